@@ -0,0 +1,87 @@
+use crate::todo::Todo;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Agenda sections in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgendaSection {
+    Overdue,
+    Today,
+    Tomorrow,
+    ThisWeek,
+    Later,
+    NoDate,
+}
+
+impl AgendaSection {
+    pub const ALL: [AgendaSection; 6] = [
+        AgendaSection::Overdue,
+        AgendaSection::Today,
+        AgendaSection::Tomorrow,
+        AgendaSection::ThisWeek,
+        AgendaSection::Later,
+        AgendaSection::NoDate,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgendaSection::Overdue => "Overdue",
+            AgendaSection::Today => "Today",
+            AgendaSection::Tomorrow => "Tomorrow",
+            AgendaSection::ThisWeek => "This week",
+            AgendaSection::Later => "Later",
+            AgendaSection::NoDate => "No date",
+        }
+    }
+
+    /// Section for a due date, none if it lies beyond the horizon
+    pub fn for_due_date(
+        due_date: Option<NaiveDate>,
+        today: NaiveDate,
+        horizon_days: i64,
+    ) -> Option<AgendaSection> {
+        let Some(due) = due_date else {
+            return Some(AgendaSection::NoDate);
+        };
+        let end_of_week = today + Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+
+        if due > today + Duration::days(horizon_days) {
+            None
+        } else if due < today {
+            Some(AgendaSection::Overdue)
+        } else if due == today {
+            Some(AgendaSection::Today)
+        } else if due == today + Duration::days(1) {
+            Some(AgendaSection::Tomorrow)
+        } else if due <= end_of_week {
+            Some(AgendaSection::ThisWeek)
+        } else {
+            Some(AgendaSection::Later)
+        }
+    }
+}
+
+/// Group open todos into agenda sections, each sorted by due date
+pub fn group_todos(
+    todos: &[Todo],
+    today: NaiveDate,
+    horizon_days: i64,
+) -> Vec<(AgendaSection, Vec<Todo>)> {
+    let mut open: Vec<&Todo> = todos.iter().filter(|todo| !todo.is_finished()).collect();
+    open.sort_by_key(|todo| todo.get_due_date());
+
+    let mut sections = Vec::new();
+    for section in AgendaSection::ALL {
+        let todos: Vec<Todo> = open
+            .iter()
+            .filter(|todo| {
+                AgendaSection::for_due_date(todo.get_due_date(), today, horizon_days)
+                    == Some(section)
+            })
+            .map(|todo| (*todo).clone())
+            .collect();
+        if !todos.is_empty() {
+            sections.push((section, todos));
+        }
+    }
+    sections
+}
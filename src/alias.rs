@@ -0,0 +1,79 @@
+use crate::config::Config;
+use crate::error::Error;
+use clap::Command;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+/// Aliases expanding to a command line, configured aliases with the same name take precedence
+const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("search", "show --search"),
+    ("find", "show --search"),
+    ("clear-completed", "remove --where finished"),
+    ("h", "help"),
+];
+
+/// Expand an alias given as the command, e.g. `utodo today` with `"today": "show --where due:today"`
+/// in the config. Commands and their short forms always win over aliases.
+pub fn expand(mut args: Vec<OsString>, cli: &Command) -> Result<Vec<OsString>, Error> {
+    let is_command = |word: &str| cli.find_subcommand(word).is_some();
+    let mut configured: Option<BTreeMap<String, String>> = None;
+    let mut expanded = Vec::new();
+
+    while let Some(position) = command_position(&args) {
+        let Some(word) = args[position].to_str().map(str::to_string) else {
+            break;
+        };
+        if is_command(&word) {
+            break;
+        }
+        // The config is only read when the command is not a built-in one
+        let aliases = match &configured {
+            Some(aliases) => aliases,
+            None => configured.insert(configured_aliases()?),
+        };
+        let Some(expansion) = aliases.get(&word).map(String::as_str).or_else(|| builtin(&word)) else {
+            break;
+        };
+        if expanded.contains(&word) {
+            return Err(Error::Config(format!("Alias '{}' expands to itself", word)));
+        }
+        let words = shlex::split(expansion)
+            .filter(|words| !words.is_empty())
+            .ok_or_else(|| {
+                Error::Config(format!("Alias '{}' is not a valid command line: {}", word, expansion))
+            })?;
+        args.splice(position..=position, words.into_iter().map(OsString::from));
+        expanded.push(word);
+    }
+    Ok(args)
+}
+
+fn builtin(name: &str) -> Option<&'static str> {
+    BUILTIN_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map(|(_, expansion)| *expansion)
+}
+
+/// Aliases from the config, none if there is no config yet
+fn configured_aliases() -> Result<BTreeMap<String, String>, Error> {
+    if !Config::get_config_file_path()?.exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(Config::load()?.get_aliases().clone())
+}
+
+/// Index of the command word, skipping the global flags in front of it
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let mut position = 1;
+    while let Some(arg) = args.get(position)?.to_str() {
+        match arg {
+            "--global" => position += 1,
+            "--list" => position += 2,
+            arg if arg.starts_with("--list=") => position += 1,
+            arg if arg.starts_with('-') => return None,
+            _ => return Some(position),
+        }
+    }
+    None
+}
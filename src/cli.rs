@@ -1,12 +1,41 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::CompleteEnv;
 
+use crate::alias;
+use crate::config::{self, Config};
+use crate::error::Error;
+use crate::sync::{self, GitRepo};
 use crate::commands::{
+    agenda::{AgendaArgs, AgendaCommand},
+    board::{BoardArgs, BoardCommand},
+    calendar::{CalendarArgs, CalendarCommand},
     add::{AddArgs, AddCommand},
     complete::{CompleteArgs, CompleteCommand},
+    completions::{self, COMPLETE_VAR, CompletionsArgs, CompletionsCommand},
+    hook::{HookArgs, HookCommand},
+    init::{InitArgs, InitCommand},
+    list::{ListArgs, ListCommand},
+    log::{LogArgs, LogCommand},
+    man::{ManArgs, ManCommand},
+    move_command::{MoveArgs, MoveCommand},
+    archive::{ArchiveArgs, ArchiveCommand, auto_archive},
+    edit::{EditArgs, EditCommand},
+    plan::{PlanArgs, PlanCommand},
+    remind::{RemindArgs, RemindCommand},
     remove::{RemoveArgs, RemoveCommand},
+    scan::{ScanArgs, ScanCommand},
+    serve::{ServeArgs, ServeCommand},
     show::{ShowArgs, ShowCommand},
     sort::{SortArgs, SortCommand},
+    start::{StartArgs, StartCommand},
+    stats::{StatsArgs, StatsCommand},
+    stop::{StopArgs, StopCommand},
+    trash::{TrashArgs, TrashCommand},
+    unarchive::{UnarchiveArgs, UnarchiveCommand},
+    timesheet::{TimesheetArgs, TimesheetCommand},
     update::{UpdateArgs, UpdateCommand},
+    view::{ViewArgs, ViewCommand},
+    workflow::{WorkflowArgs, WorkflowCommand},
 };
 
 #[derive(Parser)]
@@ -16,27 +45,194 @@ use crate::commands::{
 pub struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Operate on this list instead of the default list
+    #[arg(long, global = true, value_parser = parse_list_name)]
+    list: Option<String>,
+
+    /// Use the global todos even inside a project with a `.utodo` directory
+    #[arg(long, global = true)]
+    global: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    #[command(visible_alias = "a")]
     Add(AddArgs),
+    #[command(visible_aliases = ["rm", "delete"])]
     Remove(RemoveArgs),
+    #[command(visible_aliases = ["done", "c"])]
     Complete(CompleteArgs),
+    #[command(visible_aliases = ["ls", "l"])]
     Show(ShowArgs),
+    #[command(visible_alias = "s")]
     Sort(SortArgs),
     Update(UpdateArgs),
+    /// Manage named todo lists
+    List(ListArgs),
+    /// Initialize the configuration or a project-local `.utodo` directory
+    Init(InitArgs),
+    /// Import TODO, FIXME and HACK comments from source code
+    Scan(ScanArgs),
+    /// Close or reference todos from git commit messages
+    Hook(HookArgs),
+    /// Show statistics about open and finished todos
+    Stats(StatsArgs),
+    /// Show open todos grouped by due date
+    Agenda(AgendaArgs),
+    /// Show a month calendar of due dates
+    Calendar(CalendarArgs),
+    /// Move a todo to another workflow state
+    Move(MoveArgs),
+    /// Show todos as a board with one column per workflow state
+    Board(BoardArgs),
+    /// Show or change the workflow states
+    Workflow(WorkflowArgs),
+    /// Start a timer on a todo
+    Start(StartArgs),
+    /// Stop the running timer
+    Stop(StopArgs),
+    /// Log time spent on a todo
+    Log(LogArgs),
+    /// Show tracked time per day or week
+    Timesheet(TimesheetArgs),
+    /// Pick open todos for today that fit into the available time
+    Plan(PlanArgs),
+    /// Edit a todo in $EDITOR
+    Edit(EditArgs),
+    /// Move finished todos to the archive
+    Archive(ArchiveArgs),
+    /// Move an archived todo back to its list
+    Unarchive(UnarchiveArgs),
+    /// List, restore or empty removed todos
+    Trash(TrashArgs),
+    /// Serve a JSON API for the todos over HTTP
+    Serve(ServeArgs),
+    /// Send reminders for due, overdue and scheduled todos
+    Remind(RemindArgs),
+    /// Print a shell completion script
+    Completions(CompletionsArgs),
+    /// Print or write the man pages
+    Man(ManArgs),
+    /// Show, save or delete named views combining a filter, sort order, columns and grouping
+    View(ViewArgs),
 }
 
 impl Cli {
-    pub fn execute(self) -> Result<(), Box<dyn std::error::Error>> {
-        match self.command {
+    /// Answer the completion scripts from `completions`, exits if this run was such a request
+    pub fn complete_from_env() {
+        completions::apply_typed_list_flags();
+        CompleteEnv::with_factory(Cli::command).var(COMPLETE_VAR).complete();
+    }
+
+    /// Parse the command line of this process, expanding built-in and configured aliases first
+    pub fn parse_expanded() -> Result<Cli, Error> {
+        let args = alias::expand(std::env::args_os().collect(), &Cli::command())?;
+        Ok(Cli::parse_from(args))
+    }
+
+    pub fn execute(self) -> Result<(), Error> {
+        if let Some(list) = self.list {
+            config::set_list_override(list);
+        }
+        if self.global {
+            config::force_global();
+        }
+        // Completion scripts and man pages do not need a config
+        if !matches!(self.command, Commands::Completions(_) | Commands::Man(_)) {
+            create_config_on_first_run()?;
+        }
+        // Nobody is there to answer a prompt about sync conflicts while serving
+        if !matches!(self.command, Commands::Serve(_)) {
+            sync::resolve_conflicts_interactively();
+        }
+        retry_pending_sync();
+        let changes_todos = matches!(
+            self.command,
+            Commands::Add(_)
+                | Commands::Remove(_)
+                | Commands::Complete(_)
+                | Commands::Update(_)
+                | Commands::Scan(_)
+                | Commands::Hook(_)
+                | Commands::Move(_)
+                | Commands::Start(_)
+                | Commands::Stop(_)
+                | Commands::Log(_)
+                | Commands::Edit(_)
+        );
+        let result = match self.command {
             Commands::Add(args) => AddCommand::execute(args),
             Commands::Remove(args) => RemoveCommand::execute(args),
             Commands::Complete(args) => CompleteCommand::execute(args),
             Commands::Show(args) => ShowCommand::execute(args),
             Commands::Sort(args) => SortCommand::execute(args),
             Commands::Update(args) => UpdateCommand::execute(args),
+            Commands::List(args) => ListCommand::execute(args),
+            Commands::Init(args) => InitCommand::execute(args),
+            Commands::Scan(args) => ScanCommand::execute(args),
+            Commands::Hook(args) => HookCommand::execute(args),
+            Commands::Stats(args) => StatsCommand::execute(args),
+            Commands::Agenda(args) => AgendaCommand::execute(args),
+            Commands::Calendar(args) => CalendarCommand::execute(args),
+            Commands::Move(args) => MoveCommand::execute(args),
+            Commands::Board(args) => BoardCommand::execute(args),
+            Commands::Workflow(args) => WorkflowCommand::execute(args),
+            Commands::Start(args) => StartCommand::execute(args),
+            Commands::Stop(args) => StopCommand::execute(args),
+            Commands::Log(args) => LogCommand::execute(args),
+            Commands::Timesheet(args) => TimesheetCommand::execute(args),
+            Commands::Plan(args) => PlanCommand::execute(args),
+            Commands::Edit(args) => EditCommand::execute(args),
+            Commands::Archive(args) => ArchiveCommand::execute(args),
+            Commands::Unarchive(args) => UnarchiveCommand::execute(args),
+            Commands::Trash(args) => TrashCommand::execute(args),
+            Commands::Serve(args) => ServeCommand::execute(args),
+            Commands::Remind(args) => RemindCommand::execute(args),
+            Commands::Completions(args) => CompletionsCommand::execute(args, Cli::command()),
+            Commands::Man(args) => ManCommand::execute(args, Cli::command()),
+            Commands::View(args) => ViewCommand::execute(args),
+        };
+        // Archiving after the change keeps reading commands free of writes, the change
+        // itself is saved already, so a failure only warns
+        if changes_todos
+            && result.is_ok()
+            && let Err(e) = auto_archive()
+        {
+            eprintln!("Warning: archiving finished todos failed: {}", e);
         }
+        result
     }
 }
+
+/// Write the default config the first time `utodo` runs, so there is a file to edit
+fn create_config_on_first_run() -> Result<(), Error> {
+    if Config::exists()? {
+        return Ok(());
+    }
+    Config::create_default()?;
+    println!("📄 Created default config at: {}", Config::get_config_file_path()?.display());
+    println!("📁 Default data directory: {}", config::get_data_dir()?.display());
+    Ok(())
+}
+
+/// Push changes that could not be synced during an earlier invocation
+fn retry_pending_sync() {
+    let Ok(true) = config::get_auto_sync_enabled() else {
+        return;
+    };
+    let Ok(data_dir) = config::get_data_dir() else {
+        return;
+    };
+    let repo = GitRepo::new(data_dir);
+    match repo.retry_pending() {
+        Ok(0) => {}
+        Ok(synced) => println!("Synced {} pending change{}", synced, if synced == 1 { "" } else { "s" }),
+        Err(e) => eprintln!("Git sync still pending: {}", e),
+    }
+}
+
+fn parse_list_name(name: &str) -> Result<String, Error> {
+    config::validate_list_name(name)?;
+    Ok(name.to_string())
+}
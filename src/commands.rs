@@ -4,3 +4,47 @@ pub mod remove;
 pub mod complete;
 pub mod update;
 pub mod sort;
+pub mod list;
+pub mod init;
+pub mod scan;
+pub mod hook;
+pub mod stats;
+pub mod agenda;
+pub mod calendar;
+pub mod board;
+pub mod move_command;
+pub mod workflow;
+pub mod start;
+pub mod stop;
+pub mod log;
+pub mod timesheet;
+pub mod plan;
+pub mod edit;
+pub mod archive;
+pub mod unarchive;
+pub mod trash;
+pub mod serve;
+pub mod remind;
+pub mod completions;
+pub mod man;
+pub mod view;
+
+use crate::error::Error;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
+
+/// Parse a date given on the command line in the format dd.mm.YYYY
+pub fn parse_date_string(date_as_str: &str) -> Result<NaiveDate, Error> {
+    NaiveDate::parse_from_str(date_as_str, "%d.%m.%Y")
+        .map_err(|_| Error::Parse("Invalid date format. See help for further information".to_string()))
+}
+
+/// Parse a local date and time given on the command line in the format dd.mm.YYYY HH:MM
+pub fn parse_datetime_string(datetime_as_str: &str) -> Result<DateTime<Utc>, Error> {
+    let invalid = || Error::Parse("Invalid date and time format. See help for further information".to_string());
+    NaiveDateTime::parse_from_str(datetime_as_str.trim(), "%d.%m.%Y %H:%M")
+        .map_err(|_| invalid())?
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .ok_or_else(invalid)
+}
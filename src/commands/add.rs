@@ -1,7 +1,12 @@
+use crate::commands::{parse_date_string, parse_datetime_string};
+use crate::duration::parse_duration;
+use crate::editor::edit_todo;
+use crate::error::Error;
+use crate::events::{self, Event};
 use crate::priority::Priority;
-use crate::todo::TodoBuilder;
+use crate::todo::{Todo, TodoBuilder};
 use crate::todo_list::TodoList;
-use chrono::NaiveDate;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use clap::Args;
 
 #[derive(Debug)]
@@ -9,8 +14,8 @@ pub struct AddCommand {}
 
 #[derive(Args)]
 pub struct AddArgs {
-    #[arg(short, long)]
-    title: String,
+    #[arg(short, long, required_unless_present = "edit")]
+    title: Option<String>,
 
     #[arg(long)]
     description: Option<String>,
@@ -22,24 +27,54 @@ pub struct AddArgs {
         help = "Date in format dd.mm.YYYY or d.m.YYYY"
     )]
     due_date: Option<NaiveDate>,
+
+    #[arg(long, value_parser = parse_duration,
+        help = "Estimated effort, e.g. 30m, 2h or 1d"
+    )]
+    estimate: Option<Duration>,
+
+    #[arg(long, value_parser = parse_datetime_string,
+        help = "Send a reminder at this local time, format \"dd.mm.YYYY HH:MM\""
+    )]
+    remind: Option<DateTime<Utc>>,
+
+    #[arg(long = "tag", help = "Tag weighted in the urgency score, can be repeated")]
+    tags: Vec<String>,
+
+    /// Fill in the todo in $EDITOR, starting from the other arguments
+    #[arg(long)]
+    edit: bool,
 }
 
 impl AddCommand {
-    pub fn execute(args: AddArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let mut todo_list = TodoList::load().unwrap();
-        let todo = TodoBuilder::new()
-            .title(args.title.clone())
+    pub fn execute(args: AddArgs) -> Result<(), Error> {
+        let mut todo_list = TodoList::load()?;
+        let mut todo = TodoBuilder::new()
+            .title(args.title.unwrap_or_default())
             .due_date(args.due_date)
             .description(args.description)
             .priority(args.priority)
+            .estimate(args.estimate)
+            .remind_at(args.remind)
+            .tags(args.tags)
             .build()?;
-        todo_list.add(todo);
-        todo_list.save()?;
+        if args.edit {
+            todo = edit_todo(&todo)?;
+        }
+        add_todo(&mut todo_list, todo)?;
         Ok(())
     }
 }
 
-fn parse_date_string(date_as_str: &str) -> Result<NaiveDate, String> {
-    NaiveDate::parse_from_str(date_as_str, "%d.%m.%Y")
-        .map_err(|_| "Invalid date format. See help for further information".to_string())
+/// Add `todo` to the list and save it, running the add hooks. Returns the todo as stored.
+pub(crate) fn add_todo(todo_list: &mut TodoList, mut todo: Todo) -> Result<Todo, Error> {
+    if todo.get_title().trim().is_empty() {
+        return Err(Error::Parse("A todo needs a title".to_string()));
+    }
+    todo.set_id(todo_list.todos.len() as u32);
+    let todo = events::before(Event::Add, todo_list.get_name(), &todo)?;
+    todo_list.add(todo.clone());
+    todo_list.save()?;
+    events::after(Event::Add, todo_list.get_name(), &todo);
+    Ok(todo)
 }
@@ -0,0 +1,74 @@
+use crate::agenda;
+use crate::error::Error;
+use crate::todo::Todo;
+use crate::todo_list::TodoList;
+use chrono::Utc;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct AgendaCommand;
+
+#[derive(Args)]
+pub struct AgendaArgs {
+    /// Hide todos due more than this many days from today
+    #[arg(long, default_value_t = 14)]
+    horizon: i64,
+
+    /// One line per todo, e.g. for a shell prompt or login banner
+    #[arg(long)]
+    compact: bool,
+
+    /// Leave out todos without a due date
+    #[arg(long)]
+    no_undated: bool,
+}
+
+impl AgendaCommand {
+    pub fn execute(args: AgendaArgs) -> Result<(), Error> {
+        let todo_list = TodoList::load()?;
+        let today = Utc::now().date_naive();
+
+        for (section, todos) in agenda::group_todos(&todo_list.todos, today, args.horizon) {
+            if args.no_undated && section == agenda::AgendaSection::NoDate {
+                continue;
+            }
+            if args.compact {
+                for todo in &todos {
+                    println!("{}: {}", section.label(), compact_line(todo));
+                }
+            } else {
+                println!("{}", section.label());
+                for todo in &todos {
+                    println!("  {}", agenda_line(todo));
+                }
+                println!();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn due_date_string(todo: &Todo) -> String {
+    todo.get_due_date()
+        .map(|date| date.format("%d.%m.%Y").to_string())
+        .unwrap_or_default()
+}
+
+fn agenda_line(todo: &Todo) -> String {
+    format!(
+        "#{:<3} {:<40} {:<6} {}",
+        todo.get_id(),
+        todo.get_title(),
+        todo.get_priority(),
+        due_date_string(todo)
+    )
+    .trim_end()
+    .to_string()
+}
+
+fn compact_line(todo: &Todo) -> String {
+    match todo.get_due_date() {
+        Some(_) => format!("#{} {} ({})", todo.get_id(), todo.get_title(), due_date_string(todo)),
+        None => format!("#{} {}", todo.get_id(), todo.get_title()),
+    }
+}
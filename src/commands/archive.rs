@@ -0,0 +1,54 @@
+use crate::config;
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::todo_list::TodoList;
+use chrono::{Duration, Utc};
+use clap::Args;
+
+#[derive(Debug)]
+pub struct ArchiveCommand;
+
+#[derive(Args)]
+pub struct ArchiveArgs {
+    /// Only archive todos finished at least this many days ago
+    #[arg(long)]
+    older_than: Option<u32>,
+}
+
+impl ArchiveCommand {
+    pub fn execute(args: ArchiveArgs) -> Result<(), Error> {
+        let archived = archive_active_list(args.older_than)?;
+        println!("Archived {} todos", archived);
+        Ok(())
+    }
+}
+
+/// Archive finished todos of the active list if `auto_archive_days` is configured
+pub fn auto_archive() -> Result<(), Error> {
+    if let Some(days) = config::load_config()?.get_auto_archive_days() {
+        archive_active_list(Some(days))?;
+    }
+    Ok(())
+}
+
+fn archive_active_list(older_than_days: Option<u32>) -> Result<usize, Error> {
+    let mut todo_list = TodoList::load()?;
+    let mut archive = TodoList::load_archive()?;
+    let finished_before = older_than_days.map(|days| Utc::now() - Duration::days(days as i64));
+
+    let archived = todo_list.archive_finished(&mut archive, finished_before);
+    if archived > 0 {
+        // The archived todos are the last ones in the archive
+        let first = archive.todos.len() - archived;
+        for index in first..archive.todos.len() {
+            archive.todos[index] = events::before(Event::Archive, todo_list.get_name(), &archive.todos[index])?;
+        }
+        // Archive first, so a failure never loses todos
+        archive.save()?;
+        todo_list.save()?;
+        for todo in &archive.todos[first..] {
+            events::after(Event::Archive, todo_list.get_name(), todo);
+        }
+    }
+    Ok(archived)
+}
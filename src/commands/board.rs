@@ -0,0 +1,22 @@
+use crate::display::display_board;
+use crate::error::Error;
+use crate::todo_list::TodoList;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct BoardCommand;
+
+#[derive(Args)]
+pub struct BoardArgs {
+    /// Leave out the column of the final workflow state
+    #[arg(long)]
+    hide_done: bool,
+}
+
+impl BoardCommand {
+    pub fn execute(args: BoardArgs) -> Result<(), Error> {
+        let todo_list = TodoList::load()?;
+        display_board(&todo_list.todos, args.hide_done);
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::display::display_calendar;
+use crate::error::Error;
+use crate::todo_list::TodoList;
+use chrono::{NaiveDate, Utc};
+use clap::Args;
+
+#[derive(Debug)]
+pub struct CalendarCommand;
+
+#[derive(Args)]
+pub struct CalendarArgs {
+    #[arg(value_parser = parse_month_string,
+        help = "Month in format mm.YYYY or m.YYYY, defaults to the current month"
+    )]
+    month: Option<NaiveDate>,
+
+    /// Show the titles of the todos instead of their number
+    #[arg(long)]
+    titles: bool,
+}
+
+impl CalendarCommand {
+    pub fn execute(args: CalendarArgs) -> Result<(), Error> {
+        let todo_list = TodoList::load()?;
+        let today = Utc::now().date_naive();
+        display_calendar(&todo_list.todos, args.month.unwrap_or(today), today, args.titles);
+        Ok(())
+    }
+}
+
+fn parse_month_string(month_as_str: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("01.{}", month_as_str), "%d.%m.%Y")
+        .map_err(|_| "Invalid month format. See help for further information".to_string())
+}
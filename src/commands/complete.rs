@@ -1,29 +1,51 @@
+use crate::commands::completions::open_todo_ids;
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::selection::{IdSelection, SelectionArgs, parse_id_selection};
 use crate::todo_list::TodoList;
 use clap::Args;
+use clap_complete::ArgValueCandidates;
 
 #[derive(Debug)]
 pub struct CompleteCommand;
 
 #[derive(Args)]
 pub struct CompleteArgs {
-    id: usize,
+    /// IDs of the todos, e.g. 1,4,7-9
+    #[arg(value_parser = parse_id_selection, add = ArgValueCandidates::new(open_todo_ids))]
+    ids: Option<IdSelection>,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
 }
 
 impl CompleteCommand {
-    pub fn execute(args: CompleteArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let mut todo_list =
-            TodoList::load().map_err(|e| format!("Failed to load todo list: {}", e))?;
-
-        let todo = todo_list
-            .get_todo_mut(args.id)
-            .ok_or_else(|| format!("Todo with ID {} not found", args.id))?;
+    pub fn execute(args: CompleteArgs) -> Result<(), Error> {
+        let mut todo_list = TodoList::load()?;
 
-        todo.complete(true);
-        todo_list
-            .save()
-            .map_err(|e| format!("Failed to save todo list: {}", e))?;
+        let indices = args.selection.select(&todo_list, args.ids.as_ref())?;
+        if !args.selection.confirm(&todo_list, &indices, "Complete")? {
+            return Ok(());
+        }
 
-        println!("Todo with ID {} completed", args.id);
+        complete_todos(&mut todo_list, &indices)?;
+        for index in indices {
+            println!("Todo with ID {} completed", index);
+        }
         Ok(())
     }
 }
+
+/// Complete the todos at `indices` and save the list, running the complete hooks
+pub(crate) fn complete_todos(todo_list: &mut TodoList, indices: &[usize]) -> Result<(), Error> {
+    for index in indices {
+        let mut todo = todo_list.todos[*index].clone();
+        todo.complete(true);
+        todo_list.todos[*index] = events::before(Event::Complete, todo_list.get_name(), &todo)?;
+    }
+    todo_list.save()?;
+    for index in indices {
+        events::after(Event::Complete, todo_list.get_name(), &todo_list.todos[*index]);
+    }
+    Ok(())
+}
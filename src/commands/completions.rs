@@ -0,0 +1,100 @@
+use crate::config::{self, Config};
+use crate::error::Error;
+use crate::todo_list::TodoList;
+use clap::{Args, Command};
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+use std::io;
+
+/// Environment variable the completion scripts set when asking `utodo` for candidates
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+#[derive(Debug)]
+pub struct CompletionsCommand;
+
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to print the completion script for
+    shell: Shell,
+}
+
+impl CompletionsCommand {
+    /// Print a script that asks this binary for completions, so todo IDs complete with
+    /// their titles
+    pub fn execute(args: CompletionsArgs, cmd: Command) -> Result<(), Error> {
+        let shell = args.shell.to_string();
+        let shells = Shells::builtins();
+        let completer = shells
+            .completer(&shell)
+            .ok_or_else(|| Error::Parse(format!("Completions for {} are not supported", shell)))?;
+        let exe = std::env::current_exe()?;
+        completer.write_registration(
+            COMPLETE_VAR,
+            cmd.get_name(),
+            cmd.get_name(),
+            &exe.to_string_lossy(),
+            &mut io::stdout(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Apply `--list` and `--global` typed before the word being completed. Candidates are
+/// asked for before the command line is parsed, so they would use the default list otherwise.
+pub fn apply_typed_list_flags() {
+    if std::env::var_os(COMPLETE_VAR).is_none_or(|value| value.is_empty()) {
+        return;
+    }
+    // The scripts call `utodo -- utodo <words>`, bash and elvish also pass the index of
+    // the word being completed
+    let words: Vec<String> = std::env::args().skip_while(|arg| arg != "--").skip(1).collect();
+    let current = std::env::var("_CLAP_COMPLETE_INDEX")
+        .ok()
+        .and_then(|index| index.parse().ok())
+        .unwrap_or(words.len().saturating_sub(1))
+        .min(words.len());
+    let mut typed = words.iter().take(current).skip(1);
+    while let Some(word) = typed.next() {
+        if word == "--global" {
+            config::force_global();
+            continue;
+        }
+        let list = match word.strip_prefix("--list") {
+            Some("") => typed.next().map(String::as_str),
+            Some(rest) => rest.strip_prefix('='),
+            None => None,
+        };
+        if let Some(list) = list.filter(|list| config::validate_list_name(list).is_ok()) {
+            config::set_list_override(list.to_string());
+        }
+    }
+}
+
+/// IDs of all todos in the active list, with their titles as help
+pub fn todo_ids() -> Vec<CompletionCandidate> {
+    candidates(|_| true)
+}
+
+/// IDs of the open todos in the active list, with their titles as help
+pub fn open_todo_ids() -> Vec<CompletionCandidate> {
+    candidates(|finished| !finished)
+}
+
+fn candidates(include: impl Fn(bool) -> bool) -> Vec<CompletionCandidate> {
+    // Without a config there are no todos yet
+    let config_exists = Config::get_config_file_path().is_ok_and(|path| path.exists());
+    if !config_exists {
+        return Vec::new();
+    }
+    let Ok(todo_list) = TodoList::load() else {
+        return Vec::new();
+    };
+    todo_list
+        .todos
+        .iter()
+        .filter(|todo| include(todo.is_finished()))
+        .map(|todo| {
+            CompletionCandidate::new(todo.get_id().to_string()).help(Some(todo.get_title().into()))
+        })
+        .collect()
+}
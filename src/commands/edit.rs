@@ -0,0 +1,38 @@
+use crate::editor::{diff, edit_todo, to_document};
+use crate::error::Error;
+use crate::events;
+use crate::todo_list::TodoList;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct EditCommand;
+
+#[derive(Args)]
+pub struct EditArgs {
+    id: usize,
+}
+
+impl EditCommand {
+    pub fn execute(args: EditArgs) -> Result<(), Error> {
+        let mut todo_list = TodoList::load()?;
+        let todo = todo_list
+            .get_todo(args.id)
+            .ok_or_else(|| Error::NotFound(format!("Todo with ID {} not found", args.id)))?;
+
+        let before = to_document(todo);
+        let edited = edit_todo(todo)?;
+        let after = to_document(&edited);
+        if before == after {
+            println!("No changes");
+            return Ok(());
+        }
+
+        print!("{}", diff(&before, &after));
+        let event = events::change_event(todo, &edited);
+        todo_list.todos[args.id] = events::before(event, todo_list.get_name(), &edited)?;
+        todo_list.save()?;
+        events::after(event, todo_list.get_name(), &todo_list.todos[args.id]);
+        println!("Todo with ID {} updated", args.id);
+        Ok(())
+    }
+}
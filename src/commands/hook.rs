@@ -0,0 +1,152 @@
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::sync::GitRepo;
+use crate::todo_list::TodoList;
+use clap::{Args, Subcommand};
+use regex::Regex;
+use std::fs;
+use std::path::PathBuf;
+
+/// Marks hook scripts written by `hook install`
+const HOOK_MARKER: &str = "# installed by utodo";
+
+#[derive(Debug)]
+pub struct HookCommand;
+
+#[derive(Args)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    action: HookAction,
+}
+
+#[derive(Subcommand)]
+enum HookAction {
+    /// Install a post-commit hook handling `closes utodo#<id>` and `refs utodo#<id>`
+    Install {
+        /// Repository to install the hook into
+        #[arg(long, default_value = ".")]
+        repo: PathBuf,
+
+        /// Replace an existing post-commit hook not written by utodo
+        #[arg(long)]
+        force: bool,
+    },
+    /// Process the latest commit message, called by the installed hook
+    #[command(hide = true)]
+    PostCommit,
+}
+
+/// What a commit message asks to do with a todo
+#[derive(Debug, PartialEq)]
+enum Reference {
+    Close(usize),
+    Refer(usize),
+}
+
+impl HookCommand {
+    pub fn execute(args: HookArgs) -> Result<(), Error> {
+        match args.action {
+            HookAction::Install { repo, force } => Self::install(repo, force),
+            HookAction::PostCommit => Self::post_commit(),
+        }
+    }
+
+    fn install(repo: PathBuf, force: bool) -> Result<(), Error> {
+        let repo = GitRepo::new(repo);
+        let hooks_dir = repo
+            .hooks_dir()
+            .map_err(|e| Error::Sync(format!("Not a git repository: {}", e)))?;
+        fs::create_dir_all(&hooks_dir)?;
+
+        let hook_path = hooks_dir.join("post-commit");
+        if hook_path.exists() && !force {
+            let existing = fs::read_to_string(&hook_path).unwrap_or_default();
+            if !existing.contains(HOOK_MARKER) {
+                return Err(Error::Conflict(format!(
+                    "{} already exists, use --force to replace it",
+                    hook_path.display()
+                )));
+            }
+        }
+
+        let exe = std::env::current_exe()?;
+        let script = format!(
+            "#!/bin/sh\n{}\nexec '{}' hook post-commit\n",
+            HOOK_MARKER,
+            exe.display().to_string().replace('\'', "'\\''")
+        );
+        fs::write(&hook_path, script)?;
+        make_executable(&hook_path)?;
+
+        println!("Installed post-commit hook at {}", hook_path.display());
+        Ok(())
+    }
+
+    fn post_commit() -> Result<(), Error> {
+        let repo = GitRepo::new(std::env::current_dir()?);
+        let (hash, message) = repo.head_commit().map_err(|e| Error::Sync(e.to_string()))?;
+        let references = parse_references(&message);
+        if references.is_empty() {
+            return Ok(());
+        }
+
+        let mut todo_list = TodoList::load()?;
+        let mut changes = Vec::new();
+        for reference in references {
+            let (id, event) = match reference {
+                Reference::Close(id) => (id, Event::Complete),
+                Reference::Refer(id) => (id, Event::Update),
+            };
+            let Some(mut todo) = todo_list.get_todo(id).cloned() else {
+                eprintln!("utodo: commit references unknown todo #{}", id);
+                continue;
+            };
+            todo.add_commit(hash.clone());
+            if event == Event::Complete {
+                todo.set_finished(true);
+            }
+            todo_list.todos[id] = events::before(event, todo_list.get_name(), &todo)?;
+            changes.push((id, event));
+        }
+        todo_list.save()?;
+
+        for (id, event) in changes {
+            let todo = &todo_list.todos[id];
+            events::after(event, todo_list.get_name(), todo);
+            if event == Event::Complete {
+                println!("utodo: closed #{} {}", id, todo.get_title());
+            } else {
+                println!("utodo: linked commit to #{} {}", id, todo.get_title());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find `closes utodo#12`, `fixes utodo#12` and `refs utodo#12` in a commit message
+fn parse_references(message: &str) -> Vec<Reference> {
+    let pattern = Regex::new(r"(?i)\b(close[sd]?|fix(?:e[sd])?|refs?)\s+utodo#(\d+)")
+        .expect("invalid reference pattern");
+    pattern
+        .captures_iter(message)
+        .filter_map(|captures| {
+            let id = captures[2].parse().ok()?;
+            if captures[1].to_lowercase().starts_with("ref") {
+                Some(Reference::Refer(id))
+            } else {
+                Some(Reference::Close(id))
+            }
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn make_executable(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_: &std::path::Path) -> std::io::Result<()> {
+    Ok(())
+}
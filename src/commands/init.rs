@@ -0,0 +1,28 @@
+use crate::config;
+use crate::error::Error;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct InitCommand;
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Create a project-local `.utodo` directory in the working directory
+    #[arg(long)]
+    local: bool,
+}
+
+impl InitCommand {
+    pub fn execute(args: InitArgs) -> Result<(), Error> {
+        if args.local {
+            let local_dir = config::init_local()?;
+            println!("✅ Initialized local todos in {}", local_dir.display());
+        } else {
+            if let Some(todos_file) = config::init_config()? {
+                println!("📄 Created empty todos file: {}", todos_file.display());
+            }
+            println!("✅ Configuration initialized successfully");
+        }
+        Ok(())
+    }
+}
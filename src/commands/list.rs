@@ -0,0 +1,138 @@
+use crate::config;
+use crate::error::Error;
+use crate::todo_list::{self, TodoList};
+use clap::{Args, Subcommand};
+use std::fs;
+
+#[derive(Debug)]
+pub struct ListCommand;
+
+#[derive(Args)]
+pub struct ListArgs {
+    #[command(subcommand)]
+    action: Option<ListAction>,
+}
+
+#[derive(Subcommand)]
+enum ListAction {
+    /// Create a new empty list
+    Create { name: String },
+    /// Rename a list
+    Rename { old_name: String, new_name: String },
+    /// Delete a list and all of its todos
+    Delete { name: String },
+    /// Make a list the default for commands without --list
+    Switch { name: String },
+}
+
+impl ListCommand {
+    pub fn execute(args: ListArgs) -> Result<(), Error> {
+        match args.action {
+            None => Self::print_lists(),
+            Some(ListAction::Create { name }) => Self::create(&name),
+            Some(ListAction::Rename { old_name, new_name }) => Self::rename(&old_name, &new_name),
+            Some(ListAction::Delete { name }) => Self::delete(&name),
+            Some(ListAction::Switch { name }) => Self::switch(&name),
+        }
+    }
+
+    fn print_lists() -> Result<(), Error> {
+        let config = config::load_config()?;
+        let active = config::active_list_name()?;
+        for name in config.list_names()? {
+            let marker = if name == active { "*" } else { " " };
+            let count = TodoList::load_list(&name)?.todos.len();
+            println!("{} {} ({} todos)", marker, name, count);
+        }
+        Ok(())
+    }
+
+    fn create(name: &str) -> Result<(), Error> {
+        config::validate_list_name(name)?;
+        if list_exists(name)? {
+            return Err(Error::Conflict(format!("List '{}' already exists", name)));
+        }
+        TodoList::empty(&config::load_config()?, name).save()?;
+        println!("List '{}' created", name);
+        Ok(())
+    }
+
+    fn rename(old_name: &str, new_name: &str) -> Result<(), Error> {
+        ensure_not_builtin(old_name)?;
+        config::validate_list_name(new_name)?;
+        if !list_exists(old_name)? {
+            return Err(Error::NotFound(format!("List \'{}\' does not exist", old_name)));
+        }
+        if list_exists(new_name)? {
+            return Err(Error::Conflict(format!("List '{}' already exists", new_name)));
+        }
+
+        let mut config = config::load_config()?;
+        let local = config.is_local();
+        fs::rename(
+            config.get_list_file_path(old_name),
+            config.get_list_file_path(new_name),
+        )?;
+        let archive = config.get_archive_file_path(old_name);
+        if archive.exists() {
+            fs::rename(archive, config.get_archive_file_path(new_name))?;
+        }
+        if !local && config.get_default_list() == old_name {
+            config.set_default_list(new_name.to_string())?;
+        }
+        todo_list::sync_if_enabled()?;
+        println!("List '{}' renamed to '{}'", old_name, new_name);
+        Ok(())
+    }
+
+    fn delete(name: &str) -> Result<(), Error> {
+        ensure_not_builtin(name)?;
+        if !list_exists(name)? {
+            return Err(Error::NotFound(format!("List \'{}\' does not exist", name)));
+        }
+
+        let config = config::load_config()?;
+        if !config.is_local() && config.get_default_list() == name {
+            return Err(Error::Config(format!(
+                "List '{}' is the default list, switch to another list first",
+                name
+            )));
+        }
+        fs::remove_file(config.get_list_file_path(name))?;
+        let archive = config.get_archive_file_path(name);
+        if archive.exists() {
+            fs::remove_file(archive)?;
+        }
+        todo_list::sync_if_enabled()?;
+        println!("List '{}' deleted", name);
+        Ok(())
+    }
+
+    fn switch(name: &str) -> Result<(), Error> {
+        if !list_exists(name)? {
+            return Err(Error::NotFound(format!("List \'{}\' does not exist", name)));
+        }
+        let mut config = config::load_config()?;
+        if config.is_local() {
+            return Err(Error::Config(
+                "Project-local todos always use the default list, select others with --list".to_string(),
+            ));
+        }
+        config.set_default_list(name.to_string())?;
+        println!("Default list is now '{}'", name);
+        Ok(())
+    }
+}
+
+fn list_exists(name: &str) -> Result<bool, Error> {
+    let config = config::load_config()?;
+    Ok(config.list_names()?.iter().any(|existing| existing == name))
+}
+
+/// The default list lives in the todo file itself and cannot be renamed or deleted
+fn ensure_not_builtin(name: &str) -> Result<(), Error> {
+    if name == config::DEFAULT_LIST_NAME {
+        return Err(Error::Config(format!("The '{}' list cannot be renamed or deleted", name)));
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::commands::parse_date_string;
+use crate::duration::{format_duration, parse_duration};
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::time_tracking::TimeEntry;
+use crate::todo_list::TodoList;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use clap::Args;
+
+#[derive(Debug)]
+pub struct LogCommand;
+
+#[derive(Args)]
+pub struct LogArgs {
+    id: usize,
+
+    #[arg(value_parser = parse_duration, help = "Time spent, e.g. 30m, 2h, 1h30m or 1d")]
+    duration: Duration,
+
+    #[arg(long, value_parser = parse_date_string,
+        help = "Day the time was spent in format dd.mm.YYYY, defaults to today"
+    )]
+    date: Option<NaiveDate>,
+}
+
+impl LogCommand {
+    pub fn execute(args: LogArgs) -> Result<(), Error> {
+        let start = match args.date {
+            // Manual entries for past days start at the beginning of the working day
+            Some(date) => date.and_time(NaiveTime::from_hms_opt(9, 0, 0).expect("09:00 is a valid time")).and_utc(),
+            None => Utc::now() - args.duration,
+        };
+
+        let mut todo_list = TodoList::load()?;
+        let mut todo = todo_list
+            .get_todo(args.id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Todo with ID {} not found", args.id)))?;
+        todo.add_time_entry(TimeEntry::finished(start, args.duration));
+        todo_list.todos[args.id] = events::before(Event::Update, todo_list.get_name(), &todo)?;
+        todo_list.save()?;
+        events::after(Event::Update, todo_list.get_name(), &todo_list.todos[args.id]);
+        let title = todo_list.todos[args.id].get_title();
+
+        println!(
+            "Logged {} on #{} {}",
+            format_duration(args.duration),
+            args.id,
+            title
+        );
+        Ok(())
+    }
+}
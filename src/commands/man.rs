@@ -0,0 +1,30 @@
+use crate::error::Error;
+use clap::{Args, Command};
+use clap_mangen::Man;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct ManCommand;
+
+#[derive(Args)]
+pub struct ManArgs {
+    /// Write a page for utodo and one for each subcommand into this directory
+    /// instead of printing the main page
+    #[arg(long)]
+    dir: Option<PathBuf>,
+}
+
+impl ManCommand {
+    pub fn execute(args: ManArgs, cmd: Command) -> Result<(), Error> {
+        match args.dir {
+            Some(dir) => {
+                std::fs::create_dir_all(&dir)?;
+                clap_mangen::generate_to(cmd, &dir)?;
+                println!("Wrote man pages to {}", dir.display());
+            }
+            None => Man::new(cmd).render(&mut io::stdout())?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::Error;
+use crate::events;
+use crate::todo_list::TodoList;
+use crate::workflow;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct MoveCommand;
+
+#[derive(Args)]
+pub struct MoveArgs {
+    id: usize,
+
+    /// Workflow state to move the todo to
+    state: String,
+}
+
+impl MoveCommand {
+    pub fn execute(args: MoveArgs) -> Result<(), Error> {
+        let workflow = workflow::current();
+        if !workflow.contains(&args.state) {
+            return Err(Error::Parse(format!(
+                "Unknown state '{}', expected one of: {}",
+                args.state,
+                workflow.states().join(", ")
+            )));
+        }
+
+        let mut todo_list = TodoList::load()?;
+        let mut todo = todo_list
+            .get_todo(args.id)
+            .ok_or_else(|| Error::NotFound(format!("Todo with ID {} not found", args.id)))?
+            .clone();
+        todo.set_state(args.state.clone());
+        let event = events::change_event(&todo_list.todos[args.id], &todo);
+        todo_list.todos[args.id] = events::before(event, todo_list.get_name(), &todo)?;
+        todo_list.save()?;
+        events::after(event, todo_list.get_name(), &todo_list.todos[args.id]);
+
+        println!("Todo with ID {} moved to {}", args.id, todo_list.todos[args.id].get_state());
+        Ok(())
+    }
+}
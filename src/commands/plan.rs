@@ -0,0 +1,91 @@
+use crate::duration::{format_duration, parse_duration};
+use crate::error::Error;
+use crate::sort_order::SortCriteria;
+use crate::todo::Todo;
+use crate::todo_list::TodoList;
+use chrono::Duration;
+use clap::Args;
+
+/// Order in which open todos are considered for the plan
+const PLAN_ORDER: [SortCriteria; 3] = [
+    SortCriteria::Priority,
+    SortCriteria::DueDate,
+    SortCriteria::Estimate,
+];
+
+#[derive(Debug)]
+pub struct PlanCommand;
+
+#[derive(Args)]
+pub struct PlanArgs {
+    #[arg(long, value_parser = parse_duration,
+        help = "Time available today, e.g. 6h or 4h30m"
+    )]
+    capacity: Duration,
+}
+
+impl PlanCommand {
+    pub fn execute(args: PlanArgs) -> Result<(), Error> {
+        let todo_list = TodoList::load()?;
+        let (plan, used) = select_plan(&todo_list.todos, args.capacity);
+
+        if plan.is_empty() {
+            println!("Nothing fits into {}", format_duration(args.capacity));
+        } else {
+            println!("Plan for today:");
+            for todo in &plan {
+                let due = todo
+                    .get_due_date()
+                    .map(|date| format!(" due {}", date.format("%d.%m.%Y")))
+                    .unwrap_or_default();
+                println!(
+                    "  #{:<3} {:>7}  {:<6} {}{}",
+                    todo.get_id(),
+                    format_duration(todo.get_estimate().unwrap_or_default()),
+                    todo.get_priority(),
+                    todo.get_title(),
+                    due
+                );
+            }
+        }
+        println!(
+            "Planned {} of {}",
+            format_duration(used),
+            format_duration(args.capacity)
+        );
+
+        let unestimated = todo_list
+            .todos
+            .iter()
+            .filter(|todo| !todo.is_finished() && todo.get_estimate().is_none())
+            .count();
+        if unestimated > 0 {
+            println!(
+                "{} open todos have no estimate, set one with `update --estimate`",
+                unestimated
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Greedily pick open, estimated todos by priority, due date and estimate
+/// until the capacity is filled, skipping those that do not fit anymore
+fn select_plan(todos: &[Todo], capacity: Duration) -> (Vec<&Todo>, Duration) {
+    let mut candidates: Vec<&Todo> = todos
+        .iter()
+        .filter(|todo| !todo.is_finished() && todo.get_estimate().is_some())
+        .collect();
+    candidates.sort_by(|a, b| a.compare_by_order(b, &PLAN_ORDER));
+
+    let mut plan = Vec::new();
+    let mut used = Duration::zero();
+    for todo in candidates {
+        let estimate = todo.get_estimate().unwrap_or_default();
+        if used + estimate <= capacity {
+            used += estimate;
+            plan.push(todo);
+        }
+    }
+    (plan, used)
+}
@@ -0,0 +1,39 @@
+use crate::config;
+use crate::error::Error;
+use crate::reminder;
+use chrono::Utc;
+use clap::Args;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct RemindCommand;
+
+#[derive(Args)]
+pub struct RemindArgs {
+    /// Check once and exit, e.g. when run from cron
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between checks when running as a daemon
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+}
+
+impl RemindCommand {
+    pub fn execute(args: RemindArgs) -> Result<(), Error> {
+        if args.once {
+            reminder::check(&config::load_config()?, Utc::now())?;
+            return Ok(());
+        }
+        loop {
+            // Reload the config every time so changes to the notifier and data path apply
+            // without a restart, and keep running when a check fails. The workflow deciding
+            // which todos are finished is read once per process like in every other command.
+            if let Err(e) = config::load_config().and_then(|config| reminder::check(&config, Utc::now())) {
+                eprintln!("Checking reminders failed: {}", e);
+            }
+            thread::sleep(Duration::from_secs(args.interval));
+        }
+    }
+}
@@ -1,23 +1,64 @@
+use crate::config;
+use crate::commands::completions::todo_ids;
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::selection::{IdSelection, SelectionArgs, parse_id_selection};
+use crate::todo::Todo;
 use crate::todo_list::TodoList;
+use crate::trash::Trash;
 use clap::Args;
+use clap_complete::ArgValueCandidates;
 
 #[derive(Debug)]
 pub struct RemoveCommand;
 
 #[derive(Args)]
 pub struct RemoveArgs {
-    id: usize,
+    /// IDs of the todos, e.g. 1,4,7-9
+    #[arg(value_parser = parse_id_selection, add = ArgValueCandidates::new(todo_ids))]
+    ids: Option<IdSelection>,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
 }
 
 impl RemoveCommand {
-    pub fn execute(args: RemoveArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let mut todo_list = TodoList::load().unwrap();
-        let removed = todo_list
-            .remove(args.id)
-            .ok_or_else(|| format!("Todo with ID {} not found", args.id))?;
+    pub fn execute(args: RemoveArgs) -> Result<(), Error> {
+        let mut todo_list = TodoList::load()?;
+        let indices = args.selection.select(&todo_list, args.ids.as_ref())?;
+        if !args.selection.confirm(&todo_list, &indices, "Remove")? {
+            return Ok(());
+        }
 
-        todo_list.save()?;
-        println!("removed TODO: {:?}", removed);
+        for (index, todo) in remove_todos(&mut todo_list, &indices)? {
+            println!("Moved todo with ID {} '{}' to the trash", index, todo.get_title());
+        }
         Ok(())
     }
 }
+
+/// Move the todos at `indices` to the trash, running the remove hooks. Returns the
+/// removed todos with their former IDs in ascending order.
+pub(crate) fn remove_todos(todo_list: &mut TodoList, indices: &[usize]) -> Result<Vec<(usize, Todo)>, Error> {
+    for index in indices {
+        events::before(Event::Remove, todo_list.get_name(), &todo_list.todos[*index])?;
+    }
+
+    // Back to front, so the remaining indices stay valid
+    let mut trash = Trash::load(&config::load_config()?)?;
+    let mut removed = Vec::new();
+    for index in indices.iter().rev() {
+        if let Some(todo) = todo_list.remove(*index) {
+            removed.push((*index, todo.clone()));
+            trash.add(todo_list.get_name(), todo);
+        }
+    }
+    removed.reverse();
+    // Trash first, so a failure never loses todos
+    trash.save()?;
+    todo_list.save()?;
+    for (_, todo) in &removed {
+        events::after(Event::Remove, todo_list.get_name(), todo);
+    }
+    Ok(removed)
+}
@@ -0,0 +1,46 @@
+use crate::config;
+use crate::error::Error;
+use crate::events;
+use crate::scan;
+use crate::todo_list::TodoList;
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct ScanCommand;
+
+#[derive(Args)]
+pub struct ScanArgs {
+    /// Directory to scan for TODO, FIXME and HACK comments
+    #[arg(default_value = ".")]
+    path: PathBuf,
+}
+
+impl ScanCommand {
+    pub fn execute(args: ScanArgs) -> Result<(), Error> {
+        let root = args
+            .path
+            .canonicalize()
+            .map_err(|e| {
+                Error::NotFound(format!("Cannot scan {}: {}", args.path.display(), e))
+            })?;
+
+        let mut todo_list = TodoList::load()?;
+        let (found, files) = scan::scan_directory(&root)?;
+        let base = scan::source_base(&config::load_config()?);
+        let summary = scan::apply_scan(&mut todo_list, base.as_deref(), &root, found)?;
+        for (index, event) in &summary.changes {
+            todo_list.todos[*index] = events::before(*event, todo_list.get_name(), &todo_list.todos[*index])?;
+        }
+        todo_list.save()?;
+        for (index, event) in &summary.changes {
+            events::after(*event, todo_list.get_name(), &todo_list.todos[*index]);
+        }
+
+        println!(
+            "Scanned {} files: {} added, {} updated, {} finished",
+            files, summary.added, summary.updated, summary.finished
+        );
+        Ok(())
+    }
+}
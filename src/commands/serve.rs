@@ -0,0 +1,37 @@
+use crate::config;
+use crate::error::Error;
+use crate::server;
+use clap::Args;
+use std::net::ToSocketAddrs;
+
+#[derive(Debug)]
+pub struct ServeCommand;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on, port 0 picks a free port
+    #[arg(long, default_value = "127.0.0.1:7878")]
+    bind: String,
+}
+
+impl ServeCommand {
+    pub fn execute(args: ServeArgs) -> Result<(), Error> {
+        let config = config::load_config()?;
+        if config.get_api_token().is_none() && !is_loopback(&args.bind) {
+            return Err(Error::Config(format!(
+                "Set an api_token in the config before serving on {}",
+                args.bind
+            )));
+        }
+        server::serve(&args.bind, config.get_api_token())
+    }
+}
+
+/// Whether every address `bind` resolves to is a loopback address
+fn is_loopback(bind: &str) -> bool {
+    let Ok(addrs) = bind.to_socket_addrs() else {
+        return false;
+    };
+    let ips: Vec<_> = addrs.map(|addr| addr.ip()).collect();
+    !ips.is_empty() && ips.iter().all(|ip| ip.is_loopback())
+}
@@ -1,17 +1,130 @@
-use crate::display::{display_todo_vector};
+use crate::config::{self, Config};
+use crate::display::{display_list_todos, display_todo_vector};
+use crate::duration::format_duration;
+use crate::error::Error;
+use crate::selection::{Filter, parse_filter};
+use crate::sort_order::SortCriteria;
+use crate::sync_queue;
+use crate::time_tracking;
+use crate::todo::Todo;
 use crate::todo_list::TodoList;
+use chrono::Utc;
 use clap::Args;
 
 #[derive(Debug)]
 pub struct ShowCommand;
 
 #[derive(Args)]
-pub struct ShowArgs {}
+pub struct ShowArgs {
+    /// Show the todos of all lists in one table
+    #[arg(long)]
+    all_lists: bool,
+
+    /// Inside a project, show the global todos next to the project-local ones
+    #[arg(long)]
+    include_global: bool,
+
+    /// Sort order for this invocation instead of the configured one
+    #[arg(long, value_enum, value_delimiter = ',')]
+    sort: Option<Vec<SortCriteria>>,
+
+    /// Show the archived todos instead
+    #[arg(long)]
+    archived: bool,
+
+    /// Only todos matching the filter, e.g. "due:today and not finished"
+    #[arg(long = "where", value_parser = parse_filter)]
+    filter: Option<Filter>,
+
+    /// Only todos whose title or description contains the text, ignoring case
+    #[arg(long)]
+    search: Option<String>,
+}
+
+impl ShowArgs {
+    fn matches(&self, todo: &Todo) -> bool {
+        let search = self.search.as_ref().map(|text| text.to_lowercase());
+        self.filter.as_ref().is_none_or(|filter| filter.matches(todo))
+            && search.is_none_or(|text| {
+                todo.get_title().to_lowercase().contains(&text)
+                    || todo.get_description().to_lowercase().contains(&text)
+            })
+    }
+}
 
 impl ShowCommand {
-    pub fn execute(_: ShowArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let todos = TodoList::load()?;
-        display_todo_vector(&todos.todos_as_vec());
+    pub fn execute(args: ShowArgs) -> Result<(), Error> {
+        let config = config::load_config()?;
+        let include_global = args.include_global && config.is_local();
+        let sort_order = args.sort.clone().unwrap_or_else(|| config.get_sort_order().clone());
+
+        if args.all_lists || include_global {
+            let mut scopes = vec![("local", config.clone())];
+            if include_global {
+                scopes.push(("global", config.global()));
+            }
+
+            let mut list_todos = Vec::new();
+            for (scope, scope_config) in &scopes {
+                let label = |name: &str| {
+                    if include_global {
+                        format!("{}:{}", scope, name)
+                    } else {
+                        name.to_string()
+                    }
+                };
+                for name in list_names(scope_config, args.all_lists)? {
+                    let list = if args.archived {
+                        TodoList::load_archive_from(scope_config, &name)?
+                    } else {
+                        TodoList::load_from(scope_config, &name)?
+                    };
+                    list_todos.extend(
+                        list.todos
+                            .into_iter()
+                            .filter(|todo| args.matches(todo))
+                            .map(|todo| (label(&name), todo)),
+                    );
+                }
+            }
+            list_todos.sort_by(|(_, a), (_, b)| a.compare_by_order(b, &sort_order));
+            display_list_todos(&list_todos);
+        } else {
+            let todos = if args.archived {
+                TodoList::load_archive()?
+            } else {
+                TodoList::load()?
+            };
+            let todos: Vec<Todo> = todos
+                .sorted(&sort_order)
+                .into_iter()
+                .filter(|todo| args.matches(todo))
+                .collect();
+            display_todo_vector(&todos);
+        }
+        let pending = sync_queue::pending_count()?;
+        if pending > 0 {
+            println!("{} change{} not yet synced", pending, if pending == 1 { "" } else { "s" });
+        }
+        if let Some((list, index)) = time_tracking::find_running_timer()? {
+            let todo = &list.todos[index];
+            println!(
+                "Timer running on #{} {} ({})",
+                todo.get_id(),
+                todo.get_title(),
+                format_duration(todo.tracked_time(Utc::now()))
+            );
+        }
         Ok(())
     }
 }
+
+/// Lists to show from one storage directory: all of them or just the active one
+fn list_names(config: &Config, all_lists: bool) -> Result<Vec<String>, Error> {
+    if all_lists {
+        Ok(config.list_names()?)
+    } else {
+        Ok(vec![config::active_list_for(config)])
+    }
+}
+
@@ -1,22 +1,22 @@
 use crate::config;
+use crate::error::Error;
 use crate::sort_order::SortCriteria;
-use crate::todo_list::TodoList;
 use clap::Args;
 
 pub struct SortCommand;
 
 #[derive(Args)]
 pub struct SortArgs {
+    /// Default sort order of `show`, later criteria break ties
     #[arg(value_enum)]
     criterias: Vec<SortCriteria>,
 }
 
 impl SortCommand {
-    pub fn execute(args: SortArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let mut todo_list = TodoList::load()?;
-        todo_list.sort_by_order(&args.criterias);
+    pub fn execute(args: SortArgs) -> Result<(), Error> {
+        // Only the default order of `show` changes, the stored order keeps the IDs stable
         config::set_sort_order(args.criterias)?;
-        todo_list.save()?;
+        println!("✅ Sort order updated");
         Ok(())
     }
 }
@@ -0,0 +1,42 @@
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::time_tracking::{self, TimeEntry};
+use crate::todo_list::TodoList;
+use chrono::Utc;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct StartCommand;
+
+#[derive(Args)]
+pub struct StartArgs {
+    id: usize,
+}
+
+impl StartCommand {
+    pub fn execute(args: StartArgs) -> Result<(), Error> {
+        if let Some((list, index)) = time_tracking::find_running_timer()? {
+            let running = &list.todos[index];
+            return Err(Error::Conflict(format!(
+                "Timer already running on #{} {} in list '{}', stop it first",
+                running.get_id(),
+                running.get_title(),
+                list.get_name()
+            )));
+        }
+
+        let mut todo_list = TodoList::load()?;
+        let mut todo = todo_list
+            .get_todo(args.id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Todo with ID {} not found", args.id)))?;
+        todo.add_time_entry(TimeEntry::running(Utc::now()));
+        todo_list.todos[args.id] = events::before(Event::Update, todo_list.get_name(), &todo)?;
+        todo_list.save()?;
+        events::after(Event::Update, todo_list.get_name(), &todo_list.todos[args.id]);
+        let title = todo_list.todos[args.id].get_title();
+
+        println!("Timer started on #{} {}", args.id, title);
+        Ok(())
+    }
+}
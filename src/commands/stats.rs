@@ -0,0 +1,86 @@
+use crate::commands::parse_date_string;
+use crate::error::Error;
+use crate::stats::{self, Stats};
+use crate::todo_list::TodoList;
+use chrono::{NaiveDate, Utc};
+use clap::Args;
+use std::io;
+
+/// Width of the longest bar in the completion histogram
+const HISTOGRAM_WIDTH: usize = 30;
+
+#[derive(Debug)]
+pub struct StatsCommand;
+
+#[derive(Args)]
+pub struct StatsArgs {
+    #[arg(long, value_parser = parse_date_string,
+        help = "Only include todos from this date on, format dd.mm.YYYY"
+    )]
+    since: Option<NaiveDate>,
+
+    #[arg(long, value_parser = parse_date_string,
+        help = "Only include todos up to this date, format dd.mm.YYYY"
+    )]
+    until: Option<NaiveDate>,
+
+    /// Include the archived todos
+    #[arg(long)]
+    include_archive: bool,
+
+    /// Print the statistics as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+impl StatsCommand {
+    pub fn execute(args: StatsArgs) -> Result<(), Error> {
+        let mut todos = TodoList::load()?.todos;
+        if args.include_archive {
+            todos.extend(TodoList::load_archive()?.todos);
+        }
+        let stats = Stats::compute(&todos, args.since, args.until, Utc::now());
+
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&stats).map_err(io::Error::from)?);
+        } else {
+            print_stats(&stats);
+        }
+        Ok(())
+    }
+}
+
+fn print_stats(stats: &Stats) {
+    println!(
+        "Todos:    {} ({} open, {} finished)",
+        stats.total, stats.open, stats.finished
+    );
+    println!("Overdue:  {}", stats.overdue);
+    println!(
+        "Open by priority: High {}, Medium {}, Low {}",
+        stats.open_by_priority.high, stats.open_by_priority.medium, stats.open_by_priority.low
+    );
+    match stats.average_open_age_days {
+        Some(days) => println!("Average age of open todos: {:.1} days", days),
+        None => println!("Average age of open todos: -"),
+    }
+
+    println!("\nCompletions per week:");
+    let max = stats
+        .completions_per_week
+        .iter()
+        .map(|week| week.completed)
+        .max()
+        .unwrap_or(0);
+    for week in &stats.completions_per_week {
+        let bar_len = (week.completed * HISTOGRAM_WIDTH).checked_div(max).unwrap_or(0);
+        let line = format!("  {} {:>3} {}", week.week, week.completed, "█".repeat(bar_len));
+        println!("{}", line.trim_end());
+    }
+    let counts: Vec<usize> = stats
+        .completions_per_week
+        .iter()
+        .map(|week| week.completed)
+        .collect();
+    println!("  Trend: {}", stats::sparkline(&counts));
+}
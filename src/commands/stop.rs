@@ -0,0 +1,35 @@
+use crate::duration::format_duration;
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::time_tracking;
+use chrono::Utc;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct StopCommand;
+
+#[derive(Args)]
+pub struct StopArgs {}
+
+impl StopCommand {
+    pub fn execute(_: StopArgs) -> Result<(), Error> {
+        let (mut todo_list, index) =
+            time_tracking::find_running_timer()?.ok_or_else(|| Error::NotFound("No timer is running".to_string()))?;
+
+        let now = Utc::now();
+        let mut todo = todo_list.todos[index].clone();
+        let entry = todo.stop_timer(now).ok_or_else(|| Error::NotFound("No timer is running".to_string()))?;
+        let (id, title) = (todo.get_id(), todo.get_title());
+        todo_list.todos[index] = events::before(Event::Update, todo_list.get_name(), &todo)?;
+        todo_list.save()?;
+        events::after(Event::Update, todo_list.get_name(), &todo_list.todos[index]);
+
+        println!(
+            "Timer stopped on #{} {} after {}",
+            id,
+            title,
+            format_duration(entry.duration(now))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use crate::commands::parse_date_string;
+use crate::config;
+use crate::duration::format_duration;
+use crate::error::Error;
+use crate::todo_list::TodoList;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use clap::{Args, ValueEnum};
+use std::collections::BTreeMap;
+use std::io;
+
+#[derive(Debug)]
+pub struct TimesheetCommand;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum TimesheetPeriod {
+    #[default]
+    Day,
+    Week,
+}
+
+#[derive(Args)]
+pub struct TimesheetArgs {
+    /// Group tracked time by day or by ISO week
+    #[arg(long, value_enum, default_value_t)]
+    by: TimesheetPeriod,
+
+    #[arg(long, value_parser = parse_date_string,
+        help = "Only include time from this date on, format dd.mm.YYYY"
+    )]
+    since: Option<NaiveDate>,
+
+    #[arg(long, value_parser = parse_date_string,
+        help = "Only include time up to this date, format dd.mm.YYYY"
+    )]
+    until: Option<NaiveDate>,
+
+    /// Print the timesheet as CSV
+    #[arg(long)]
+    csv: bool,
+}
+
+/// Time tracked on one todo within one period
+struct TimesheetRow {
+    list: String,
+    id: u32,
+    title: String,
+    duration: Duration,
+}
+
+impl TimesheetCommand {
+    pub fn execute(args: TimesheetArgs) -> Result<(), Error> {
+        let now = Utc::now();
+        let config = config::load_config()?;
+
+        // Period label -> (list, id) -> row, ordered by period
+        let mut periods: BTreeMap<String, BTreeMap<(String, u32), TimesheetRow>> = BTreeMap::new();
+        for name in config.list_names()? {
+            let list = TodoList::load_from(&config, &name)?;
+            for todo in &list.todos {
+                for entry in todo.get_time_entries() {
+                    let day = entry.start.date_naive();
+                    if args.since.is_some_and(|since| day < since)
+                        || args.until.is_some_and(|until| day > until)
+                    {
+                        continue;
+                    }
+                    let row = periods
+                        .entry(period_label(day, args.by))
+                        .or_default()
+                        .entry((name.clone(), todo.get_id()))
+                        .or_insert_with(|| TimesheetRow {
+                            list: name.clone(),
+                            id: todo.get_id(),
+                            title: todo.get_title(),
+                            duration: Duration::zero(),
+                        });
+                    row.duration += entry.duration(now);
+                }
+            }
+        }
+
+        if args.csv {
+            print_csv(&periods)
+        } else {
+            print_text(&periods);
+            Ok(())
+        }
+    }
+}
+
+fn period_label(day: NaiveDate, period: TimesheetPeriod) -> String {
+    match period {
+        TimesheetPeriod::Day => day.format("%Y-%m-%d").to_string(),
+        TimesheetPeriod::Week => {
+            let week = day.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+    }
+}
+
+fn total<'a>(rows: impl Iterator<Item = &'a TimesheetRow>) -> Duration {
+    rows.fold(Duration::zero(), |total, row| total + row.duration)
+}
+
+fn print_text(periods: &BTreeMap<String, BTreeMap<(String, u32), TimesheetRow>>) {
+    if periods.is_empty() {
+        println!("No time tracked");
+        return;
+    }
+    for (period, rows) in periods {
+        println!("{}  {}", period, format_duration(total(rows.values())));
+        for row in rows.values() {
+            println!(
+                "  {:>8}  {}#{} {}",
+                format_duration(row.duration),
+                list_prefix(&row.list),
+                row.id,
+                row.title
+            );
+        }
+    }
+    let grand_total = total(periods.values().flat_map(|rows| rows.values()));
+    println!("Total  {}", format_duration(grand_total));
+}
+
+/// Todos of the default list are shown without their list name
+fn list_prefix(list: &str) -> String {
+    if list == config::DEFAULT_LIST_NAME {
+        String::new()
+    } else {
+        format!("{}:", list)
+    }
+}
+
+fn print_csv(
+    periods: &BTreeMap<String, BTreeMap<(String, u32), TimesheetRow>>,
+) -> Result<(), Error> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    // Writing to stdout is the only thing that can fail here
+    writer
+        .write_record(["period", "list", "id", "title", "minutes", "hours"])
+        .map_err(io::Error::from)?;
+    for (period, rows) in periods {
+        for row in rows.values() {
+            let minutes = row.duration.num_minutes();
+            writer.write_record([
+                period.clone(),
+                row.list.clone(),
+                row.id.to_string(),
+                row.title.clone(),
+                minutes.to_string(),
+                format!("{:.2}", minutes as f64 / 60.0),
+            ])
+            .map_err(io::Error::from)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
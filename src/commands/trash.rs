@@ -0,0 +1,93 @@
+use crate::config;
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::todo_list::{self, TodoList};
+use crate::trash::Trash;
+use clap::{Args, Subcommand};
+
+#[derive(Debug)]
+pub struct TrashCommand;
+
+#[derive(Args)]
+pub struct TrashArgs {
+    #[command(subcommand)]
+    action: Option<TrashAction>,
+}
+
+#[derive(Subcommand)]
+enum TrashAction {
+    /// Show the removed todos
+    List,
+    /// Move a removed todo back to the list it was removed from
+    Restore { id: usize },
+    /// Delete all removed todos for good
+    Empty,
+}
+
+impl TrashCommand {
+    pub fn execute(args: TrashArgs) -> Result<(), Error> {
+        match args.action.unwrap_or(TrashAction::List) {
+            TrashAction::List => Self::list(),
+            TrashAction::Restore { id } => Self::restore(id),
+            TrashAction::Empty => Self::empty(),
+        }
+    }
+
+    fn list() -> Result<(), Error> {
+        let trash = Trash::load(&config::load_config()?)?;
+        if trash.is_empty() {
+            println!("The trash is empty");
+            return Ok(());
+        }
+        for (id, entry) in trash.entries().iter().enumerate() {
+            println!(
+                "  #{} {} (from {}, removed {})",
+                id,
+                entry.todo.get_title(),
+                entry.list,
+                entry.deleted_at.format("%H:%M %d.%m.%Y")
+            );
+        }
+        Ok(())
+    }
+
+    fn restore(id: usize) -> Result<(), Error> {
+        let config = config::load_config()?;
+        let mut trash = Trash::load(&config)?;
+        let entry = trash
+            .remove(id)
+            .ok_or_else(|| Error::NotFound(format!("Removed todo with ID {} not found", id)))?;
+
+        // Lists deleted in the meantime fall back to the active list
+        let mut todo_list = match TodoList::load_from(&config, &entry.list) {
+            Err(Error::NotFound(_)) => TodoList::load_from(&config, &config::active_list_for(&config))?,
+            loaded => loaded?,
+        };
+        let todo = events::before(Event::Add, todo_list.get_name(), &entry.todo)?;
+        let title = todo.get_title();
+        todo_list.add(todo);
+        // List first, so a failure never loses todos
+        todo_list.save()?;
+        trash.save()?;
+        todo_list::sync_if_enabled()?;
+        events::after(Event::Add, todo_list.get_name(), &todo_list.todos[todo_list.todos.len() - 1]);
+        println!(
+            "Restored '{}' to list '{}' as todo with ID {}",
+            title,
+            todo_list.get_name(),
+            todo_list.todos.len() - 1
+        );
+        Ok(())
+    }
+
+    fn empty() -> Result<(), Error> {
+        let config = config::load_config()?;
+        let mut trash = Trash::load(&config)?;
+        let count = trash.len();
+        trash.clear();
+        trash.save()?;
+        todo_list::sync_if_enabled()?;
+        println!("Deleted {} todos for good", count);
+        Ok(())
+    }
+}
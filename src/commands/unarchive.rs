@@ -0,0 +1,33 @@
+use crate::error::Error;
+use crate::events::{self, Event};
+use crate::todo_list::TodoList;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct UnarchiveCommand;
+
+#[derive(Args)]
+pub struct UnarchiveArgs {
+    /// ID of the todo as shown by `show --archived`
+    id: usize,
+}
+
+impl UnarchiveCommand {
+    pub fn execute(args: UnarchiveArgs) -> Result<(), Error> {
+        let mut archive = TodoList::load_archive()?;
+        let todo = archive
+            .remove(args.id)
+            .ok_or_else(|| Error::NotFound(format!("Archived todo with ID {} not found", args.id)))?;
+
+        let mut todo_list = TodoList::load()?;
+        let todo = events::before(Event::Unarchive, todo_list.get_name(), &todo)?;
+        let title = todo.get_title();
+        todo_list.add(todo);
+        // List first, so a failure never loses todos
+        todo_list.save()?;
+        archive.save()?;
+        events::after(Event::Unarchive, todo_list.get_name(), &todo_list.todos[todo_list.todos.len() - 1]);
+        println!("Restored '{}' as todo with ID {}", title, todo_list.todos.len() - 1);
+        Ok(())
+    }
+}
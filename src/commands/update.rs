@@ -1,15 +1,24 @@
+use crate::commands::{parse_date_string, parse_datetime_string};
+use crate::duration::parse_duration;
+use crate::commands::completions::todo_ids;
+use crate::error::Error;
+use crate::events;
 use crate::priority::Priority;
+use crate::selection::{IdSelection, SelectionArgs, parse_id_selection};
+use crate::todo::Todo;
 use crate::todo_list::TodoList;
-use chrono::NaiveDate;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use clap::Args;
+use clap_complete::ArgValueCandidates;
 
 #[derive(Debug)]
 pub struct UpdateCommand;
 
 #[derive(Args)]
 pub struct UpdateArgs {
-    #[arg(long)]
-    id: usize,
+    /// IDs of the todos, e.g. 1,4,7-9
+    #[arg(long = "id", value_parser = parse_id_selection, add = ArgValueCandidates::new(todo_ids))]
+    ids: Option<IdSelection>,
 
     #[arg(short, long)]
     title: Option<String>,
@@ -25,39 +34,94 @@ pub struct UpdateArgs {
     )]
     due_date: Option<NaiveDate>,
 
+    #[arg(long, value_parser = parse_duration,
+        help = "Estimated effort, e.g. 30m, 2h or 1d"
+    )]
+    estimate: Option<Duration>,
+
+    #[arg(long, value_parser = parse_datetime_string,
+        help = "Send a reminder at this local time, format \"dd.mm.YYYY HH:MM\""
+    )]
+    remind: Option<DateTime<Utc>>,
+
+    #[arg(long = "tag", help = "Add a tag, can be repeated")]
+    tags: Vec<String>,
+
+    #[arg(long = "untag", help = "Remove a tag, can be repeated")]
+    untags: Vec<String>,
+
     #[arg(short, long)]
     finished: Option<bool>,
-}
 
-fn parse_date_string(date_as_str: &str) -> Result<NaiveDate, String> {
-    NaiveDate::parse_from_str(date_as_str, "%d.%m.%Y")
-        .map_err(|_| "Invalid date format. See help for further information".to_string())
+    #[command(flatten)]
+    selection: SelectionArgs,
 }
 
 impl UpdateCommand {
-    pub fn execute(args: UpdateArgs) -> Result<(), Box<dyn std::error::Error>> {
-        let mut todo_list = TodoList::load().unwrap();
-        if let Some(todo) = todo_list.get_todo_mut(args.id) {
-            if let Some(title) = args.title {
+    pub fn execute(args: UpdateArgs) -> Result<(), Error> {
+        let mut todo_list = TodoList::load()?;
+        let indices = args.selection.select(&todo_list, args.ids.as_ref())?;
+        if !args.selection.confirm(&todo_list, &indices, "Update")? {
+            return Ok(());
+        }
+
+        change_todos(&mut todo_list, &indices, |todo| {
+            if let Some(title) = &args.title {
                 todo.set_title(title.clone());
             }
-            if let Some(priority) = args.priority {
-                todo.set_priority(priority);
+            if let Some(priority) = &args.priority {
+                todo.set_priority(priority.clone());
             }
             if let Some(due_date) = args.due_date {
                 todo.set_due_date(due_date);
             }
-            if let Some(description) = args.description {
-                todo.set_description(description);
+            if let Some(estimate) = args.estimate {
+                todo.set_estimate(estimate);
+            }
+            if let Some(remind_at) = args.remind {
+                todo.set_remind_at(remind_at);
+            }
+            for tag in &args.tags {
+                todo.add_tag(tag.clone());
+            }
+            for tag in &args.untags {
+                todo.remove_tag(tag);
+            }
+            if let Some(description) = &args.description {
+                todo.set_description(description.clone());
             }
             if let Some(finished) = args.finished {
                 todo.set_finished(finished);
             }
-            todo_list.save()?;
-            println!("Todo with ID: {} updated", args.id);
-        } else {
-            return Err(format!("Todo with ID: {} not found", args.id).into());
+        })?;
+        for index in indices {
+            println!("Todo with ID: {} updated", index);
         }
         Ok(())
     }
 }
+
+/// Apply `change` to the todos at `indices` and save the list, running the update hooks,
+/// or the complete hooks for todos the change finishes
+pub(crate) fn change_todos(
+    todo_list: &mut TodoList,
+    indices: &[usize],
+    change: impl Fn(&mut Todo),
+) -> Result<(), Error> {
+    let mut changes = Vec::new();
+    for index in indices {
+        let mut todo = todo_list.todos[*index].clone();
+        change(&mut todo);
+        if todo.get_title().trim().is_empty() {
+            return Err(Error::Parse("A todo needs a title".to_string()));
+        }
+        let event = events::change_event(&todo_list.todos[*index], &todo);
+        todo_list.todos[*index] = events::before(event, todo_list.get_name(), &todo)?;
+        changes.push((*index, event));
+    }
+    todo_list.save()?;
+    for (index, event) in changes {
+        events::after(event, todo_list.get_name(), &todo_list.todos[index]);
+    }
+    Ok(())
+}
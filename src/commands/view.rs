@@ -0,0 +1,156 @@
+use crate::config;
+use crate::display::display_view;
+use crate::error::Error;
+use crate::selection::parse_filter;
+use crate::sort_order::SortCriteria;
+use crate::todo::Todo;
+use crate::todo_list::TodoList;
+use crate::view::{self, Column, GroupBy, View};
+use clap::{Args, Subcommand};
+
+/// Names taken by the subcommands of `view`
+const RESERVED_NAMES: [&str; 4] = ["save", "list", "delete", "help"];
+
+#[derive(Debug)]
+pub struct ViewCommand;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ViewArgs {
+    #[command(subcommand)]
+    action: Option<ViewAction>,
+
+    /// Name of the view to show, lists the views if omitted
+    name: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum ViewAction {
+    /// Save a view, replacing a saved or built-in view of the same name
+    Save {
+        name: String,
+
+        /// Only todos matching the filter, e.g. "open and tag:work"
+        #[arg(long = "where", value_parser = filter_expression)]
+        filter: Option<String>,
+
+        /// Sort order of the view instead of the configured one
+        #[arg(long, value_enum, value_delimiter = ',')]
+        sort: Vec<SortCriteria>,
+
+        /// Columns to show instead of those of `show`
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+
+        /// Show one table per priority, workflow state, due date or tag
+        #[arg(long, value_enum)]
+        group_by: Option<GroupBy>,
+    },
+    /// Show the saved and built-in views
+    List,
+    /// Delete a saved view
+    Delete { name: String },
+}
+
+impl ViewCommand {
+    pub fn execute(args: ViewArgs) -> Result<(), Error> {
+        match (args.action, args.name) {
+            (Some(ViewAction::Save { name, filter, sort, columns, group_by }), _) => {
+                Self::save(name, View { filter, sort, columns, group_by })
+            }
+            (Some(ViewAction::Delete { name }), _) => Self::delete(&name),
+            (Some(ViewAction::List), _) | (None, None) => Self::list(),
+            (None, Some(name)) => Self::show(&name),
+        }
+    }
+
+    fn show(name: &str) -> Result<(), Error> {
+        let config = config::load_config()?;
+        let view = config
+            .get_views()
+            .get(name)
+            .cloned()
+            .or_else(|| builtin(name))
+            .ok_or_else(|| Error::NotFound(format!("View '{}' not found", name)))?;
+        let filter = view.parsed_filter()?;
+        let sort_order = if view.sort.is_empty() {
+            config.get_sort_order().clone()
+        } else {
+            view.sort.clone()
+        };
+
+        let todos: Vec<Todo> = TodoList::load()?
+            .sorted(&sort_order)
+            .into_iter()
+            .filter(|todo| filter.as_ref().is_none_or(|filter| filter.matches(todo)))
+            .collect();
+        display_view(&view.group(name, todos), view.columns());
+        Ok(())
+    }
+
+    fn save(name: String, view: View) -> Result<(), Error> {
+        validate_view_name(&name)?;
+        let mut config = config::load_config()?;
+        let replaced = config.get_views().contains_key(&name);
+        config.set_view(name.clone(), view)?;
+        if replaced {
+            println!("View '{}' updated", name);
+        } else {
+            println!("View '{}' saved", name);
+        }
+        Ok(())
+    }
+
+    fn list() -> Result<(), Error> {
+        let config = config::load_config()?;
+        let saved = config.get_views();
+        for (name, view) in saved {
+            println!("  {}: {}", name, view.describe());
+        }
+        for (name, view) in view::builtin_views() {
+            if !saved.contains_key(name) {
+                println!("  {} (built-in): {}", name, view.describe());
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(name: &str) -> Result<(), Error> {
+        let mut config = config::load_config()?;
+        if config.remove_view(name)? {
+            println!("View '{}' deleted", name);
+            return Ok(());
+        }
+        if builtin(name).is_some() {
+            return Err(Error::Config(format!("The built-in view '{}' cannot be deleted", name)));
+        }
+        Err(Error::NotFound(format!("View '{}' not found", name)))
+    }
+}
+
+fn builtin(name: &str) -> Option<View> {
+    view::builtin_views()
+        .into_iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, view)| view)
+}
+
+/// View names are typed after `view`, so they cannot be empty, contain spaces or shadow a subcommand
+fn validate_view_name(name: &str) -> Result<(), Error> {
+    if name.is_empty() || name.starts_with('-') || name.chars().any(char::is_whitespace) {
+        return Err(Error::Parse(format!("Invalid view name '{}'", name)));
+    }
+    if RESERVED_NAMES.contains(&name) {
+        return Err(Error::Parse(format!(
+            "'{}' is a view subcommand and cannot be used as a view name",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a filter like `--where` of `show` does, keeping it as written for the config
+fn filter_expression(filter: &str) -> Result<String, Error> {
+    parse_filter(filter)?;
+    Ok(filter.to_string())
+}
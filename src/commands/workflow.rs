@@ -0,0 +1,25 @@
+use crate::config;
+use crate::error::Error;
+use crate::workflow::Workflow;
+use clap::Args;
+
+#[derive(Debug)]
+pub struct WorkflowCommand;
+
+#[derive(Args)]
+pub struct WorkflowArgs {
+    /// New workflow states in order, the last one marks todos as finished
+    states: Vec<String>,
+}
+
+impl WorkflowCommand {
+    pub fn execute(args: WorkflowArgs) -> Result<(), Error> {
+        let mut config = config::load_config()?;
+        if !args.states.is_empty() {
+            config.set_workflow(args.states)?;
+        }
+        let workflow = Workflow::new(config.get_workflow().to_vec())?;
+        println!("Workflow: {}", workflow.states().join(" → "));
+        Ok(())
+    }
+}
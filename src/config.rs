@@ -1,11 +1,40 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::fs;
+use std::io;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::error::Error;
+use crate::events::EventHooks;
+use crate::reminder::Notifier;
 use crate::sort_order::SortCriteria;
+use crate::urgency::UrgencyCoefficients;
+use crate::view::View;
+use crate::workflow::{self, Workflow};
 
 pub const TODO_FILE_NAME: &str = "todos.json";
 pub const CONFIG_FILE_NAME: &str = "config.json";
+pub const SYNC_QUEUE_FILE_NAME: &str = "sync_queue.json";
+pub const TRASH_FILE_NAME: &str = "trash.json";
+/// Reminders `remind` already sent, kept per machine and not synced
+pub const REMINDERS_FILE_NAME: &str = "reminders.json";
+/// Days removed todos stay in the trash when the config does not say otherwise
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+/// Directory below the data path holding all named lists except the default one
+pub const LISTS_DIR_NAME: &str = "lists";
+/// Directory below the data path holding the archived todos of each list
+pub const ARCHIVE_DIR_NAME: &str = "archive";
+/// The built-in list stored in TODO_FILE_NAME
+pub const DEFAULT_LIST_NAME: &str = "default";
+
+/// Project-local data directory, discovered by walking up from the working directory
+pub const LOCAL_DIR_NAME: &str = ".utodo";
+
+/// List selected with the global `--list` flag for this invocation
+static LIST_OVERRIDE: OnceLock<String> = OnceLock::new();
+/// Set by the global `--global` flag to ignore project-local todos
+static FORCE_GLOBAL: AtomicBool = AtomicBool::new(false);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
@@ -17,70 +46,135 @@ pub struct Config {
     pub git_remote: Option<String>,
     // Sort order
     sort_order: Vec<SortCriteria>,
+    /// List used when no `--list` flag is given
+    #[serde(default = "default_list_name")]
+    default_list: String,
+    /// Workflow states, the last one marks a todo as finished
+    #[serde(default = "default_workflow")]
+    workflow: Vec<String>,
+    /// Weights of the urgency score
+    #[serde(default)]
+    urgency: UrgencyCoefficients,
+    /// Archive todos this many days after they were finished, off if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_archive_days: Option<u32>,
+    /// Days removed todos stay in the trash, 0 keeps them until the trash is emptied
+    #[serde(default = "default_trash_retention_days")]
+    trash_retention_days: u32,
+    /// Bearer token required by `serve`, the API is open if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    api_token: Option<String>,
+    /// How `remind` delivers reminders
+    #[serde(default)]
+    notifier: Notifier,
+    /// Commands run on todo lifecycle events and around syncs
+    #[serde(default, skip_serializing_if = "EventHooks::is_default")]
+    hooks: EventHooks,
+    /// Names expanding to a command line, e.g. `"today": "show --where due:today"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    aliases: BTreeMap<String, String>,
+    /// Named views saved with `view save`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    views: BTreeMap<String, View>,
+    /// Project-local data directory in effect for this invocation
+    #[serde(skip)]
+    local_path: Option<PathBuf>,
+}
+
+fn default_list_name() -> String {
+    DEFAULT_LIST_NAME.to_string()
+}
+
+fn default_trash_retention_days() -> u32 {
+    DEFAULT_TRASH_RETENTION_DAYS
+}
+
+fn default_workflow() -> Vec<String> {
+    workflow::DEFAULT_STATES.iter().map(|state| state.to_string()).collect()
 }
 
 impl Config {
     /// Get the path to the config file
-    fn config_file_path() -> Result<PathBuf, Box<dyn Error>> {
+    fn config_file_path() -> Result<PathBuf, Error> {
         let config_dir = dirs::config_dir()
-            .ok_or("Could not find config directory")?;
-        
-        let app_config_dir = config_dir.join("unsafeToDo");
-        
-        // Ensure config directory exists
-        fs::create_dir_all(&app_config_dir)?;
+            .ok_or_else(|| Error::Config("Could not find config directory".to_string()))?;
         
-        Ok(app_config_dir.join(CONFIG_FILE_NAME))
+        // Created by `save`, reading the config leaves the file system alone
+        Ok(config_dir.join("unsafeToDo").join(CONFIG_FILE_NAME))
     }
     
     /// Get default data directory (XDG standard)
-    fn default_data_path() -> Result<PathBuf, Box<dyn Error>> {
+    fn default_data_path() -> Result<PathBuf, Error> {
         let data_dir = dirs::data_local_dir()
-            .ok_or("Could not find local data directory")?;
-        
-        let app_data_dir = data_dir.join("unsafeToDo");
+            .ok_or_else(|| Error::Config("Could not find local data directory".to_string()))?;
         
-        // Ensure data directory exists
-        fs::create_dir_all(&app_data_dir)?;
-        
-        Ok(app_data_dir)
+        // Created once the config is saved or todos are stored
+        Ok(data_dir.join("unsafeToDo"))
     }
     
-     fn default() -> Result<Self, Box<dyn Error>> {
+     fn default() -> Result<Self, Error> {
         Ok(Config {
             data_path: Self::default_data_path()?,
             auto_sync_enabled: false,
             sort_order: vec![SortCriteria::default()],
             git_remote: None,
+            default_list: default_list_name(),
+            workflow: default_workflow(),
+            urgency: UrgencyCoefficients::default(),
+            auto_archive_days: None,
+            trash_retention_days: default_trash_retention_days(),
+            api_token: None,
+            notifier: Notifier::default(),
+            hooks: EventHooks::default(),
+            aliases: BTreeMap::new(),
+            views: BTreeMap::new(),
+            local_path: None,
         })
     }
     
-    /// Load config from file, create with defaults if doesn't exist
-    pub fn load() -> Result<Self, Box<dyn Error>> {
+    /// Whether a config file was written already
+    pub fn exists() -> Result<bool, Error> {
+        Ok(Self::config_file_path()?.exists())
+    }
+
+    /// Write the default config, returning it
+    pub fn create_default() -> Result<Self, Error> {
+        let config = Self::default()?;
+        config.save()?;
+        Ok(Config {
+            local_path: find_local_dir(),
+            ..config
+        })
+    }
+
+    /// Load config from file, or the defaults without writing anything if there is none yet
+    pub fn load() -> Result<Self, Error> {
         let config_path = Self::config_file_path()?;
         
         if config_path.exists() {
             let content = fs::read_to_string(&config_path)?;
-            let config: Config = serde_json::from_str(&content)?;
-            
-            // Ensure data directory exists
-            fs::create_dir_all(&config.data_path)?;
-            
+            let mut config: Config = serde_json::from_str(&content).map_err(|e| {
+                Error::Config(format!("Invalid config file {}: {}", config_path.display(), e))
+            })?;
+            Workflow::new(config.workflow.clone()).map_err(|e| {
+                Error::Config(format!("Invalid workflow in {}: {}", config_path.display(), e))
+            })?;
+            validate_list_name(&config.default_list).map_err(|e| {
+                Error::Config(format!("Invalid default_list in {}: {}", config_path.display(), e))
+            })?;
+
+            config.local_path = find_local_dir();
             Ok(config)
         } else {
-            // Create default config and save it
-            let default_config = Self::default()?;
-            default_config.save()?;
-            
-            println!("📄 Created default config at: {}", config_path.display());
-            println!("📁 Default data directory: {}", default_config.data_path.display());
-            
-            Ok(default_config)
+            Ok(Config {
+                local_path: find_local_dir(),
+                ..Self::default()?
+            })
         }
     }
     
     /// Save config to file
-    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+    pub fn save(&self) -> Result<(), Error> {
         let config_path = Self::config_file_path()?;
         
         // Ensure config directory exists
@@ -91,7 +185,7 @@ impl Config {
         // Ensure data directory exists
         fs::create_dir_all(&self.data_path)?;
         
-        let json = serde_json::to_string_pretty(self)?;
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
         fs::write(&config_path, json)?;
         Ok(())
     }
@@ -100,9 +194,137 @@ impl Config {
     pub fn get_todos_file_path(&self) -> PathBuf {
         self.data_path.join(TODO_FILE_NAME)
     }
+
+    /// Directory lists are stored in: the project-local one if present, else the data path
+    pub fn storage_path(&self) -> &Path {
+        self.local_path.as_deref().unwrap_or(&self.data_path)
+    }
+
+    /// Whether lists are read from a project-local `.utodo` directory
+    pub fn is_local(&self) -> bool {
+        self.local_path.is_some()
+    }
+
+    /// The same config with project-local storage ignored
+    pub fn global(&self) -> Config {
+        Config {
+            local_path: None,
+            ..self.clone()
+        }
+    }
+
+    /// Get the full path to the file of a named list
+    pub fn get_list_file_path(&self, name: &str) -> PathBuf {
+        self.storage_path().join(list_file_name(name))
+    }
+
+    /// Get the full path to the archive of a named list
+    pub fn get_archive_file_path(&self, name: &str) -> PathBuf {
+        self.storage_path().join(ARCHIVE_DIR_NAME).join(list_file_name(name))
+    }
+
+    /// Names of all lists in the storage directory, default list first
+    pub fn list_names(&self) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        let lists_dir = self.storage_path().join(LISTS_DIR_NAME);
+        if lists_dir.exists() {
+            for entry in fs::read_dir(lists_dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "json")
+                    && let Some(stem) = path.file_stem()
+                {
+                    names.push(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+        names.sort();
+        names.insert(0, default_list_name());
+        Ok(names)
+    }
+
+    /// Get the list used when no `--list` flag is given
+    pub fn get_default_list(&self) -> &str {
+        &self.default_list
+    }
+
+    /// Set the default list and save config
+    pub fn set_default_list(&mut self, name: String) -> Result<(), Error> {
+        self.default_list = name;
+        self.save()?;
+        Ok(())
+    }
+    
+    /// Get the configured workflow states
+    pub fn get_workflow(&self) -> &[String] {
+        &self.workflow
+    }
+
+    /// Days after which finished todos are archived automatically
+    pub fn get_auto_archive_days(&self) -> Option<u32> {
+        self.auto_archive_days
+    }
+
+    /// Days removed todos stay in the trash before they are purged
+    pub fn get_trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+    }
+
+    /// Bearer token clients of the HTTP API have to send
+    pub fn get_api_token(&self) -> Option<&str> {
+        self.api_token.as_deref()
+    }
+
+    /// How `remind` delivers reminders
+    pub fn get_notifier(&self) -> &Notifier {
+        &self.notifier
+    }
+
+    /// Commands run on todo lifecycle events
+    pub fn get_hooks(&self) -> &EventHooks {
+        &self.hooks
+    }
+
+    /// Command aliases defined by the user
+    pub fn get_aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    /// Views saved with `view save`
+    pub fn get_views(&self) -> &BTreeMap<String, View> {
+        &self.views
+    }
+
+    /// Save a view under `name`, replacing a view of the same name, and save config
+    pub fn set_view(&mut self, name: String, view: View) -> Result<(), Error> {
+        self.views.insert(name, view);
+        self.save()?;
+        Ok(())
+    }
+
+    /// Delete a saved view and save config, returning whether it existed
+    pub fn remove_view(&mut self, name: &str) -> Result<bool, Error> {
+        if self.views.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Get the weights of the urgency score
+    pub fn get_urgency(&self) -> &UrgencyCoefficients {
+        &self.urgency
+    }
+
+    /// Set the workflow states and save config
+    pub fn set_workflow(&mut self, states: Vec<String>) -> Result<(), Error> {
+        Workflow::new(states.clone())?;
+        self.workflow = states;
+        self.save()?;
+        Ok(())
+    }
     
     /// Set data path and save config
-    pub fn set_data_path(&mut self, new_path: PathBuf) -> Result<(), Box<dyn Error>> {
+    pub fn set_data_path(&mut self, new_path: PathBuf) -> Result<(), Error> {
         // Ensure the new directory exists
         fs::create_dir_all(&new_path)?;
         
@@ -112,31 +334,30 @@ impl Config {
     }
     
     /// Set auto-sync enabled/disabled and save config
-    pub fn set_auto_sync(&mut self, enabled: bool) -> Result<(), Box<dyn Error>> {
+    pub fn set_auto_sync(&mut self, enabled: bool) -> Result<(), Error> {
         self.auto_sync_enabled = enabled;
         self.save()?;
         Ok(())
     }
 
     /// Set git remote path and save config
-    pub fn set_git_remote(&mut self, path: String) -> Result<(), Box<dyn Error>> {
+    pub fn set_git_remote(&mut self, path: String) -> Result<(), Error> {
         self.git_remote = Some(path);
         self.save()?;
         Ok(())
     }
     
-    /// Move todos from old path to new path
-    pub fn migrate_todos(&self, old_path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    /// Copy the todos from old path to new path, returning whether there were any to copy
+    pub fn migrate_todos(&self, old_path: &Path) -> Result<bool, Error> {
         let old_todos_file = old_path.join(TODO_FILE_NAME);
         let new_todos_file = self.get_todos_file_path();
         
         if old_todos_file.exists() && !new_todos_file.exists() {
             fs::copy(&old_todos_file, &new_todos_file)?;
-            println!("📋 Migrated todos from {} to {}", 
-                old_todos_file.display(), new_todos_file.display());
+            return Ok(true);
         }
         
-        Ok(())
+        Ok(false)
     }
 
     /// Get current sort order
@@ -145,33 +366,109 @@ impl Config {
     }
 
     /// Set sort order and save config
-    pub fn set_sort_order(&mut self, sort_order: Vec<SortCriteria>) -> Result<(), Box<dyn Error>> {
+    pub fn set_sort_order(&mut self, sort_order: Vec<SortCriteria>) -> Result<(), Error> {
         self.sort_order = sort_order;
         self.save()?;
         Ok(())
     }
     
-    pub fn get_config_file_path() -> Result<PathBuf, Box<dyn Error>> {
+    pub fn get_config_file_path() -> Result<PathBuf, Error> {
         Self::config_file_path()
     }
 }
 
 // Public API functions for easy use
 
-/// Load configuration (creates default if doesn't exist)
-pub fn load_config() -> Result<Config, Box<dyn Error>> {
+/// Load configuration (the defaults if there is no config file yet)
+pub fn load_config() -> Result<Config, Error> {
     Config::load()
 }
 
-/// Get the path to TODO_FILE_NAME based on current config
+/// File name of a list relative to the data directory
+pub fn list_file_name(name: &str) -> String {
+    if name == DEFAULT_LIST_NAME {
+        TODO_FILE_NAME.to_string()
+    } else {
+        format!("{}/{}.json", LISTS_DIR_NAME, name)
+    }
+}
+
+/// List names become file names, so only allow a safe subset of characters
+pub fn validate_list_name(name: &str) -> Result<(), Error> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !valid {
+        return Err(Error::Parse(format!(
+            "Invalid list name '{}': use letters, digits, '-', '_' and '.'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Select the list for this invocation, overriding the configured default
+pub fn set_list_override(name: String) {
+    LIST_OVERRIDE.set(name).ok();
+}
+
+/// Name of the list commands operate on
+pub fn active_list_name() -> Result<String, Error> {
+    Ok(active_list_for(&Config::load()?))
+}
+
+/// Name of the list commands operate on in the storage directory of `config`
+pub fn active_list_for(config: &Config) -> String {
+    if let Some(name) = LIST_OVERRIDE.get() {
+        return name.clone();
+    }
+    // The configured default list belongs to the global storage
+    if config.is_local() {
+        return default_list_name();
+    }
+    config.default_list.clone()
+}
+
+/// Ignore project-local todos for this invocation
+pub fn force_global() {
+    FORCE_GLOBAL.store(true, Ordering::Relaxed);
+}
+
+/// Walk up from the working directory to find a project-local data directory
+pub fn find_local_dir() -> Option<PathBuf> {
+    if FORCE_GLOBAL.load(Ordering::Relaxed) {
+        return None;
+    }
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(LOCAL_DIR_NAME))
+        .find(|dir| dir.is_dir())
+}
+
+/// Create a project-local data directory in the working directory
+pub fn init_local() -> Result<PathBuf, Error> {
+    let local_dir = std::env::current_dir()?.join(LOCAL_DIR_NAME);
+    if local_dir.exists() {
+        return Err(Error::Conflict(format!("{} already exists", local_dir.display())));
+    }
+    fs::create_dir_all(&local_dir)?;
+    fs::write(local_dir.join(TODO_FILE_NAME), "")?;
+    Ok(local_dir)
+}
+
+/// Get the path to the active list file based on current config
 pub fn get_data_path() -> PathBuf {
     match Config::load() {
-        Ok(config) => config.get_todos_file_path(),
+        Ok(config) => {
+            let name = active_list_name().unwrap_or_else(|_| default_list_name());
+            config.get_list_file_path(&name)
+        }
         Err(_) => {
             // Fallback to old behavior if config fails
             let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
             path.push("unsafe_todo");
-            fs::create_dir_all(&path).ok();
             path.push(TODO_FILE_NAME);
             path
         }
@@ -179,13 +476,13 @@ pub fn get_data_path() -> PathBuf {
 }
 
 /// Get the data directory (without TODO_FILE_NAME)
-pub fn get_data_dir() -> Result<PathBuf, Box<dyn Error>> {
+pub fn get_data_dir() -> Result<PathBuf, Error> {
     let config = Config::load()?;
     Ok(config.data_path)
 }
 
 /// Set data path where todos are stored
-pub fn set_data_path(new_path: PathBuf) -> Result<(), Box<dyn Error>> {
+pub fn set_data_path(new_path: PathBuf) -> Result<(), Error> {
     let mut config = Config::load()?;
     let old_path = config.data_path.clone();
     
@@ -194,67 +491,63 @@ pub fn set_data_path(new_path: PathBuf) -> Result<(), Box<dyn Error>> {
     // Try to migrate existing todos
     config.migrate_todos(&old_path)?;
     
-    println!("✅ Data path updated to: {}", new_path.display());
-    println!("📁 Todos file: {}", config.get_todos_file_path().display());
-    
     Ok(())
 }
 
 /// Validate current configuration
-pub fn validate_config() -> Result<(), Box<dyn Error>> {
-    let config = Config::load()?;
-    println!("✅ Configuration is valid");
+pub fn validate_config() -> Result<(), Error> {
+    Config::load()?;
     Ok(())
 }
 
-/// Initialize data directory and config
-pub fn init_config() -> Result<(), Box<dyn Error>> {
-    let config = Config::load()?; // This creates default if needed
+/// Initialize data directory and config, returning the todos file if it was created
+pub fn init_config() -> Result<Option<PathBuf>, Error> {
+    let config = if Config::exists()? {
+        Config::load()?
+    } else {
+        Config::create_default()?
+    };
     
     // Create TODO_FILE_NAME if it doesn't exist
     let todos_file = config.get_todos_file_path();
-    if !todos_file.exists() {
-        fs::write(&todos_file, "[]")?; // Empty JSON array
-        println!("📄 Created empty todos file: {}", todos_file.display());
+    if todos_file.exists() {
+        return Ok(None);
     }
-    
-    println!("✅ Configuration initialized successfully");
-    Ok(())
+    fs::create_dir_all(&config.data_path)?;
+    fs::write(&todos_file, "")?; // One todo per line, none yet
+    Ok(Some(todos_file))
 }
 
 /// Get current sort order from config
-pub fn get_sort_order() -> Result<Vec<SortCriteria>, Box<dyn Error>> {
+pub fn get_sort_order() -> Result<Vec<SortCriteria>, Error> {
     let config = Config::load()?;
     Ok(config.sort_order.clone())
 }
 
 /// Get auto_sync_enabled from config
-pub fn get_auto_sync_enabled() -> Result<bool, Box<dyn Error>> {
+pub fn get_auto_sync_enabled() -> Result<bool, Error> {
     let config = Config::load()?;
-    Ok(config.auto_sync_enabled.clone())
+    Ok(config.auto_sync_enabled)
 }
 
 /// Set sort order in config
-pub fn set_sort_order(sort_order: Vec<SortCriteria>) -> Result<(), Box<dyn Error>> {
+pub fn set_sort_order(sort_order: Vec<SortCriteria>) -> Result<(), Error> {
     let mut config = Config::load()?;
     config.set_sort_order(sort_order.clone())?;
-    println!("✅ Sort order updated");
     Ok(())
 }
 
 
 /// Set git_remote path in config
-pub fn set_git_remote(path: String) -> Result<(), Box<dyn Error>> {
+pub fn set_git_remote(path: String) -> Result<(), Error> {
     let mut config = Config::load()?;
-    config.set_git_remote(path.clone())?;
-    println!("Git remote path updated to: {}", path);
+    config.set_git_remote(path)?;
     Ok(())
 }
 
 /// Set auto_sync in config
-pub fn set_auto_sync(enabled: bool) -> Result<(), Box<dyn Error>> {
+pub fn set_auto_sync(enabled: bool) -> Result<(), Error> {
     let mut config = Config::load()?;
-    config.set_auto_sync(enabled.clone())?;
-    println!("Git auto sync updated to: {}", enabled);
+    config.set_auto_sync(enabled)?;
     Ok(())
 }
\ No newline at end of file
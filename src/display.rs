@@ -1,5 +1,11 @@
+use crate::duration::format_duration;
+use crate::priority::Priority as TodoPriority;
 use crate::todo::Todo;
+use crate::view::Column;
+use crate::workflow;
+use chrono::{Datelike, Duration, NaiveDate};
 use tabled::Table;
+use tabled::builder::Builder;
 use tabled::Tabled;
 use tabled::settings::Alignment;
 use tabled::settings::Color;
@@ -19,6 +25,8 @@ struct TodoDisplay {
     description: String,
     finished: String,
     priority: String,
+    urgency: String,
+    estimate: String,
     created_at: String,
     due_date: String,
 }
@@ -27,17 +35,34 @@ impl TodoDisplay {
     pub fn from(todo: &Todo) -> Self {
         let id = todo.get_id().to_string();
         let title = todo.get_title();
-        let description = todo.get_description();
+        // Todos imported by `scan` point back to their comment
+        let description = match todo.get_source() {
+            Some(source) if todo.get_description().is_empty() => source.display(),
+            _ => todo.get_description(),
+        };
         let finished = (if todo.is_finished() { 
             "✅" 
         }
         else if todo.is_overdue() {
             "❌"
         }
+        else if todo.get_state() != workflow::current().initial() {
+            // Intermediate workflow states like "doing" are shown by name
+            todo.get_state()
+        }
         else { 
             "⏳" 
         }).to_string();
         let priority = todo.get_priority().to_string();
+        let urgency = if todo.is_finished() {
+            String::new()
+        } else {
+            format!("{:.1}", todo.urgency())
+        };
+        let estimate = todo
+            .get_estimate()
+            .map(format_duration)
+            .unwrap_or_default();
         let created_at = todo
             .get_creation_date()
             .format("%H:%M %d.%m.%Y")
@@ -53,6 +78,8 @@ impl TodoDisplay {
             description,
             finished,
             priority,
+            urgency,
+            estimate,
             created_at,
             due_date,
         }
@@ -60,24 +87,87 @@ impl TodoDisplay {
 }
 
 
-pub fn display_todo_vector(todo_vec: &Vec<Todo>) {
+#[derive(Tabled)]
+struct ListTodoDisplay {
+    list: String,
+    #[tabled(inline)]
+    todo: TodoDisplay,
+}
+
+pub fn display_todo_vector(todo_vec: &[Todo]) {
     let mut todos = vec![];
     for todo in todo_vec.iter() {
         todos.push(TodoDisplay::from(todo));
     }
+    print_table(Table::new(todos), Some("Todos"), todo_vec, Some(3), true);
+}
+
+/// Display todos of several lists in one table with an additional list column
+pub fn display_list_todos(list_todos: &[(String, Todo)]) {
+    let mut rows = vec![];
+    for (list, todo) in list_todos.iter() {
+        rows.push(ListTodoDisplay {
+            list: list.clone(),
+            todo: TodoDisplay::from(todo),
+        });
+    }
+    let todos: Vec<Todo> = list_todos.iter().map(|(_, todo)| todo.clone()).collect();
+    print_table(Table::new(rows), Some("Todos"), &todos, Some(4), true);
+}
+
+/// Display todos in one table per group with only the chosen columns
+pub fn display_view(groups: &[(String, Vec<Todo>)], columns: &[Column]) {
+    let finished_column = columns.iter().position(|column| *column == Column::Finished);
+    for (title, todo_vec) in groups {
+        let mut builder = Builder::default();
+        builder.push_record(columns.iter().map(|column| column.header()));
+        for todo in todo_vec {
+            let display = TodoDisplay::from(todo);
+            builder.push_record(columns.iter().map(|column| match column {
+                Column::Id => display.id.clone(),
+                Column::Title => display.title.clone(),
+                Column::Description => display.description.clone(),
+                Column::Finished => display.finished.clone(),
+                Column::Priority => display.priority.clone(),
+                Column::Urgency => display.urgency.clone(),
+                Column::Estimate => display.estimate.clone(),
+                Column::CreatedAt => display.created_at.clone(),
+                Column::DueDate => display.due_date.clone(),
+                Column::Tags => todo.get_tags().join(", "),
+            }));
+        }
+        // A border title would lose characters at narrow column borders, so name the group above.
+        // Several groups share the terminal, so only a single table fills it.
+        println!("{} ({})", title, todo_vec.len());
+        print_table(builder.build(), None, todo_vec, finished_column, groups.len() == 1);
+    }
+}
+
+fn print_table(
+    mut table: Table,
+    title: Option<&str>,
+    todo_vec: &[Todo],
+    finished_column: Option<usize>,
+    fill_height: bool,
+) {
     let (width, height) = get_terminal_size();
-    let mut table = Table::new(todos);
+    table.with(Style::rounded());
+    if let Some(title) = title {
+        table.with(LineText::new(title, Rows::first()).offset(2));
+    }
     table
-        .with(Style::rounded())
-        .with(LineText::new("Todos", Rows::first()).offset(2))
         .with(Width::wrap(width).priority(Priority::max(true)))
-        .with(Width::increase(width))
-        .with(Height::limit(height))
-        .with(Height::increase(height))
+        .with(Width::increase(width));
+    if fill_height {
+        table.with(Height::limit(height)).with(Height::increase(height));
+    }
+    table
         .modify(Locator::content("Low"), Color::FG_GREEN)
         .modify(Locator::content("Medium"), Color::FG_YELLOW)
-        .modify(Locator::content("High"), Color::FG_RED)
-        .modify(Columns::single(3), Alignment::center());
+        .modify(Locator::content("High"), Color::FG_RED);
+    if let Some(finished_column) = finished_column {
+        table.modify(Columns::single(finished_column), Alignment::center());
+    }
 
     for (i, todo) in todo_vec.iter().enumerate() {
         let is_overdue = todo.is_overdue();
@@ -89,10 +179,149 @@ pub fn display_todo_vector(todo_vec: &Vec<Todo>) {
     println!("{table}");
 }
 
+/// Longest title shown on a board card
+const BOARD_TITLE_WIDTH: usize = 24;
+
+/// Draw one column per workflow state with the todos in that state
+pub fn display_board(todo_vec: &[Todo], hide_done: bool) {
+    let workflow = workflow::current();
+    let states: Vec<&String> = workflow
+        .states()
+        .iter()
+        .filter(|state| !(hide_done && workflow.is_done(state)))
+        .collect();
+
+    // Todos in states no longer part of the workflow land in the first column, or the last if finished
+    let columns: Vec<Vec<&Todo>> = states
+        .iter()
+        .map(|state| {
+            todo_vec
+                .iter()
+                .filter(|todo| workflow.states()[todo.workflow_position()] == **state)
+                .collect()
+        })
+        .collect();
+
+    let mut builder = Builder::default();
+    builder.push_record(
+        states
+            .iter()
+            .zip(&columns)
+            .map(|(state, todos)| format!("{} ({})", state, todos.len())),
+    );
+    let rows = columns.iter().map(|todos| todos.len()).max().unwrap_or(0);
+    let mut overdue = Vec::new();
+    for row in 0..rows {
+        let mut cells = Vec::new();
+        for (column, todos) in columns.iter().enumerate() {
+            match todos.get(row) {
+                Some(todo) => {
+                    if todo.is_overdue() && !todo.is_finished() {
+                        overdue.push((row + 1, column));
+                    }
+                    cells.push(format!(
+                        "#{} {}",
+                        todo.get_id(),
+                        truncate(&todo.get_title(), BOARD_TITLE_WIDTH)
+                    ));
+                }
+                None => cells.push(String::new()),
+            }
+        }
+        builder.push_record(cells);
+    }
+
+    let mut table = builder.build();
+    table
+        .with(Style::rounded())
+        .with(LineText::new("Board", Rows::first()).offset(2));
+    for position in overdue {
+        table.modify(position, Color::FG_RED);
+    }
+    println!("{table}");
+}
+
+/// Longest title shown in a calendar day cell
+const CALENDAR_TITLE_WIDTH: usize = 14;
+
+/// Draw a month grid with the open todos due on each day, colored like the todo table
+pub fn display_calendar(todo_vec: &[Todo], month: NaiveDate, today: NaiveDate, show_titles: bool) {
+    let first = month.with_day(1).expect("every month has a first day");
+    let grid_start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+
+    let mut builder = Builder::default();
+    builder.push_record(["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]);
+    let mut colors = Vec::new();
+    let mut day = grid_start;
+    let mut row = 1;
+    while day.month() == first.month() || day < first {
+        let mut cells = Vec::new();
+        for column in 0..7 {
+            if day.month() != first.month() {
+                cells.push(String::new());
+                day += Duration::days(1);
+                continue;
+            }
+
+            let due: Vec<&Todo> = todo_vec
+                .iter()
+                .filter(|todo| !todo.is_finished() && todo.get_due_date() == Some(day))
+                .collect();
+            let marker = if day == today { "*" } else { "" };
+            let mut cell = format!("{}{}", day.day(), marker);
+            if show_titles {
+                for todo in &due {
+                    cell.push('\n');
+                    cell.push_str(&truncate(&todo.get_title(), CALENDAR_TITLE_WIDTH));
+                }
+            } else if !due.is_empty() {
+                cell.push_str(&format!("\n{} todo{}", due.len(), if due.len() == 1 { "" } else { "s" }));
+            }
+            cells.push(cell);
+
+            if due.iter().any(|todo| todo.is_overdue()) {
+                colors.push(((row, column), Color::FG_RED));
+            } else if let Some(highest) = due.iter().map(|todo| todo.get_priority().priority_value()).max() {
+                let color = match highest {
+                    value if value >= TodoPriority::High.priority_value() => Color::FG_RED,
+                    value if value >= TodoPriority::Medium.priority_value() => Color::FG_YELLOW,
+                    _ => Color::FG_GREEN,
+                };
+                colors.push(((row, column), color));
+            }
+            day += Duration::days(1);
+        }
+        builder.push_record(cells);
+        row += 1;
+    }
+
+    let mut table = builder.build();
+    table
+        .with(Style::rounded())
+        .with(LineText::new(first.format("%B %Y").to_string(), Rows::first()).offset(2));
+    for (position, color) in colors {
+        table.modify(position, color);
+    }
+    println!("{table}");
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
 
 fn get_terminal_size() -> (usize, usize) {
+    // Fallback for pipes and non-interactive shells, honoring $COLUMNS if set
+    let fallback_width = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(120);
     let (TerminalWidth(width), TerminalHeight(height)) =
-        terminal_size().expect("failed to obtain a terminal size");
+        terminal_size().unwrap_or((TerminalWidth(fallback_width), TerminalHeight(24)));
 
     (width as usize, height as usize)
 }
@@ -0,0 +1,59 @@
+use crate::error::Error;
+use chrono::Duration;
+
+/// Minutes in a working day, used for the `d` unit
+const MINUTES_PER_DAY: i64 = 8 * 60;
+
+/// Longest accepted duration, a million hours, so sums and dates shifted by it stay in range
+const MAX_MINUTES: i64 = 1_000_000 * 60;
+
+/// Parse durations like `30m`, `2h`, `1h30m`, `1.5h` or `1d` (one working day of 8 hours)
+pub fn parse_duration(duration_as_str: &str) -> Result<Duration, Error> {
+    let error = || {
+        Error::Parse(format!(
+            "Invalid duration '{}'. Use e.g. 30m, 2h, 1h30m or 1d",
+            duration_as_str
+        ))
+    };
+
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    // Whitespace is allowed, so formatted durations like `1h 30m` parse again
+    for c in duration_as_str.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let value: f64 = number.parse().map_err(|_| error())?;
+        minutes += match c {
+            'm' => value,
+            'h' => value * 60.0,
+            'd' => value * MINUTES_PER_DAY as f64,
+            _ => return Err(error()),
+        };
+        number.clear();
+    }
+    if !number.is_empty() || minutes <= 0.0 {
+        return Err(error());
+    }
+    // Out of range values saturate in the cast and are rejected below
+    duration_from_minutes(minutes.round() as i64).ok_or_else(error)
+}
+
+/// Duration of `minutes`, if it is positive and not longer than `parse_duration` accepts
+pub fn duration_from_minutes(minutes: i64) -> Option<Duration> {
+    if minutes <= 0 || minutes > MAX_MINUTES {
+        return None;
+    }
+    Duration::try_minutes(minutes)
+}
+
+/// Format a duration as hours and minutes, e.g. `1h 30m`
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
@@ -0,0 +1,189 @@
+use crate::commands::{parse_date_string, parse_datetime_string};
+use crate::duration::{format_duration, parse_duration};
+use crate::priority::Priority;
+use crate::todo::Todo;
+use crate::workflow;
+use chrono::Local;
+use std::env;
+use crate::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
+
+/// Line separating the fields from the description
+const FRONT_MATTER: &str = "---";
+
+/// Render a todo as front-matter fields followed by the description as Markdown body
+pub fn to_document(todo: &Todo) -> String {
+    let due = todo
+        .get_due_date()
+        .map(|date| date.format("%d.%m.%Y").to_string())
+        .unwrap_or_default();
+    let remind = todo
+        .get_remind_at()
+        .map(|remind_at| remind_at.with_timezone(&Local).format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_default();
+    let estimate = todo.get_estimate().map(format_duration).unwrap_or_default();
+    let mut document = format!(
+        "{marker}\ntitle: {}\npriority: {}\nstate: {}\ndue: {}\nremind: {}\nestimate: {}\ntags: {}\n{marker}\n",
+        todo.get_title(),
+        todo.get_priority(),
+        todo.get_state(),
+        due,
+        remind,
+        estimate,
+        todo.get_tags().join(", "),
+        marker = FRONT_MATTER,
+    );
+    let description = todo.get_description();
+    if !description.is_empty() {
+        document.push_str(&description);
+        document.push('\n');
+    }
+    document
+}
+
+/// Apply an edited document to a copy of `todo`, rejecting unknown or invalid fields
+pub fn from_document(document: &str, todo: &Todo) -> Result<Todo, String> {
+    let mut lines = document.lines();
+    if lines.next().map(str::trim) != Some(FRONT_MATTER) {
+        return Err(format!("The document has to start with '{}'", FRONT_MATTER));
+    }
+
+    let mut edited = todo.clone();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim() == FRONT_MATTER {
+            closed = true;
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Expected 'field: value', found '{}'", line))?;
+        let value = value.trim();
+        match key.trim() {
+            "title" if value.is_empty() => return Err("The title must not be empty".to_string()),
+            "title" => edited.set_title(value.to_string()),
+            "priority" => edited.set_priority(value.parse::<Priority>()?),
+            "state" if !workflow::current().contains(value) => {
+                return Err(format!("Unknown workflow state '{}'", value));
+            }
+            "state" if value != edited.get_state() => edited.set_state(value.to_string()),
+            "state" => {}
+            "due" if value.is_empty() => edited.clear_due_date(),
+            "due" => edited.set_due_date(parse_date_string(value).map_err(|e| e.to_string())?),
+            "remind" if value.is_empty() => edited.clear_remind_at(),
+            "remind" => edited.set_remind_at(parse_datetime_string(value).map_err(|e| e.to_string())?),
+            "estimate" if value.is_empty() => edited.clear_estimate(),
+            "estimate" => edited.set_estimate(parse_duration(value).map_err(|e| e.to_string())?),
+            "tags" => edited.set_tags(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            other => return Err(format!("Unknown field '{}'", other)),
+        }
+    }
+    if !closed {
+        return Err(format!("The fields have to end with '{}'", FRONT_MATTER));
+    }
+
+    let description = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    if description != todo.get_description() {
+        edited.set_description(description);
+    }
+    Ok(edited)
+}
+
+/// Edit a todo as a document until it is valid or the user gives up
+pub fn edit_todo(todo: &Todo) -> Result<Todo, Error> {
+    let mut document = to_document(todo);
+    loop {
+        document = edit_text(&document)?;
+        match from_document(&document, todo) {
+            Ok(edited) => return Ok(edited),
+            Err(e) => {
+                eprintln!("Invalid todo: {}", e);
+                print!("Reopen the editor? [y/N] ");
+                io::stdout().flush()?;
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+                    return Err(Error::Parse(e));
+                }
+            }
+        }
+    }
+}
+
+/// Open `document` in `$VISUAL` or `$EDITOR` and return the saved text
+pub fn edit_text(document: &str) -> Result<String, Error> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Created exclusively with a random name, so nothing planted in the temp directory is followed
+    let mut file = tempfile::Builder::new().prefix("utodo-").suffix(".md").tempfile()?;
+    file.write_all(document.as_bytes())?;
+    file.flush()?;
+
+    // Through the shell, so editors configured with arguments like "code --wait" work
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(file.path())
+        .status();
+    // Editors may replace the file instead of writing to it, so read it back by path
+    let edited = fs::read_to_string(file.path());
+    file.close()?;
+
+    if !status?.success() {
+        return Err(Error::Io(io::Error::other(format!(
+            "Editor '{}' exited with an error",
+            editor
+        ))));
+    }
+    Ok(edited?)
+}
+
+/// Line diff of two documents, unchanged lines are indented, removed ones start
+/// with `-` and added ones with `+`
+pub fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, documents are small
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            output.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            output.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        } else {
+            output.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        }
+    }
+    output
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned by the utodo library, each category exits `utodo` with its own code
+#[derive(Debug)]
+pub enum Error {
+    /// A todo, list or timer that does not exist
+    NotFound(String),
+    /// Invalid input, e.g. a malformed date or filter or an unknown workflow state
+    Parse(String),
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// A stored file could not be read back
+    Corrupt {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// Running git or reaching the remote failed
+    Sync(String),
+    /// The config could not be read or does not allow the change
+    Config(String),
+    /// The change conflicts with what exists, e.g. a list that already exists or a running timer
+    Conflict(String),
+    /// A `pre_*` hook rejected the change
+    Hook(String),
+}
+
+/// Result type of the utodo library
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Process exit code for this kind of error, 2 is left to usage errors
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::NotFound(_) => 3,
+            Error::Parse(_) => 4,
+            Error::Io(_) => 5,
+            Error::Corrupt { .. } => 6,
+            Error::Sync(_) => 7,
+            Error::Config(_) => 8,
+            Error::Conflict(_) => 9,
+            Error::Hook(_) => 10,
+        }
+    }
+
+    /// A file that is not valid JSON at `line` (1-based)
+    pub fn corrupt(path: impl Into<PathBuf>, line: usize, error: impl fmt::Display) -> Self {
+        Error::Corrupt {
+            path: path.into(),
+            line,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Corrupt { path, line, message } => {
+                write!(f, "{} is corrupt at line {}: {}", path.display(), line, message)
+            }
+            Error::Sync(message) => write!(f, "Sync failed: {}", message),
+            Error::NotFound(message)
+            | Error::Parse(message)
+            | Error::Config(message)
+            | Error::Conflict(message)
+            | Error::Hook(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
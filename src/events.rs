@@ -0,0 +1,292 @@
+use crate::config;
+use crate::error::Error;
+use crate::todo::Todo;
+use crate::workflow;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds a hook may run when the config does not say otherwise
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 10;
+
+/// Shell commands run on todo lifecycle events, set with `hooks` in the config.
+///
+/// `pre_*` hooks run before a change is saved and get the todo as it will be stored on
+/// stdin. Exiting non-zero, timing out or printing invalid JSON rejects the change;
+/// printing a todo as JSON stores that todo instead (`pre_remove` can only reject).
+/// `on_*` hooks and `post_sync` run after the change was saved, their failures are
+/// only reported.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EventHooks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_add: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_add: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_update: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_update: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_complete: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_complete: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_remove: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_remove: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_archive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_archive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_unarchive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_unarchive: Option<String>,
+    /// Runs before syncing with the git remote, failing skips the sync until the next change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_sync: Option<String>,
+    /// Runs after a sync attempt, `UTODO_SYNC_RESULT` is `ok` or `failed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_sync: Option<String>,
+    /// Seconds a hook may run before it is killed
+    pub timeout_seconds: u64,
+}
+
+impl Default for EventHooks {
+    fn default() -> Self {
+        EventHooks {
+            pre_add: None,
+            on_add: None,
+            pre_update: None,
+            on_update: None,
+            pre_complete: None,
+            on_complete: None,
+            pre_remove: None,
+            on_remove: None,
+            pre_archive: None,
+            on_archive: None,
+            pre_unarchive: None,
+            on_unarchive: None,
+            pre_sync: None,
+            post_sync: None,
+            timeout_seconds: DEFAULT_HOOK_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+impl EventHooks {
+    pub fn is_default(&self) -> bool {
+        *self == EventHooks::default()
+    }
+}
+
+/// A change to a single todo that hooks can observe
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    Add,
+    Update,
+    Complete,
+    Remove,
+    /// A finished todo moves from its list to the archive
+    Archive,
+    /// An archived todo moves back to its list
+    Unarchive,
+}
+
+impl Event {
+    fn name(self) -> &'static str {
+        match self {
+            Event::Add => "add",
+            Event::Update => "update",
+            Event::Complete => "complete",
+            Event::Remove => "remove",
+            Event::Archive => "archive",
+            Event::Unarchive => "unarchive",
+        }
+    }
+}
+
+/// Event of changing `old` into `new`: finishing a todo completes it, anything else updates it
+pub fn change_event(old: &Todo, new: &Todo) -> Event {
+    if !old.is_finished() && new.is_finished() {
+        Event::Complete
+    } else {
+        Event::Update
+    }
+}
+
+/// Hooks from the config, loaded once per process
+pub fn hooks() -> &'static EventHooks {
+    static HOOKS: OnceLock<EventHooks> = OnceLock::new();
+    HOOKS.get_or_init(|| {
+        config::load_config()
+            .map(|config| config.get_hooks().clone())
+            .unwrap_or_default()
+    })
+}
+
+/// Run the `pre_*` hook of `event`, returning the todo to store
+pub fn before(event: Event, list: &str, todo: &Todo) -> Result<Todo, Error> {
+    let hooks = hooks();
+    let command = match event {
+        Event::Add => &hooks.pre_add,
+        Event::Update => &hooks.pre_update,
+        Event::Complete => &hooks.pre_complete,
+        Event::Remove => &hooks.pre_remove,
+        Event::Archive => &hooks.pre_archive,
+        Event::Unarchive => &hooks.pre_unarchive,
+    };
+    let Some(command) = command else {
+        return Ok(todo.clone());
+    };
+
+    let name = format!("pre_{}", event.name());
+    let stdout = run(&name, command, &serde_json::to_string(todo).map_err(io::Error::from)?, &[("UTODO_LIST", list)])?;
+    if event == Event::Remove || stdout.trim().is_empty() {
+        return Ok(todo.clone());
+    }
+    let changed = Todo::from_json_line(stdout.trim(), todo.get_id())
+        .map_err(|e| Error::Hook(format!("{} hook printed an invalid todo: {}", name, e)))?;
+    if !workflow::current().contains(changed.get_state()) {
+        return Err(Error::Hook(format!(
+            "{} hook set the unknown workflow state '{}'",
+            name,
+            changed.get_state()
+        )));
+    }
+    Ok(changed)
+}
+
+/// Run the `on_*` hook of `event` after the change was saved
+pub fn after(event: Event, list: &str, todo: &Todo) {
+    let hooks = hooks();
+    let command = match event {
+        Event::Add => &hooks.on_add,
+        Event::Update => &hooks.on_update,
+        Event::Complete => &hooks.on_complete,
+        Event::Remove => &hooks.on_remove,
+        Event::Archive => &hooks.on_archive,
+        Event::Unarchive => &hooks.on_unarchive,
+    };
+    let Some(command) = command else {
+        return;
+    };
+    let name = format!("on_{}", event.name());
+    let result = serde_json::to_string(todo)
+        .map_err(|e| Error::Io(e.into()))
+        .and_then(|input| run(&name, command, &input, &[("UTODO_LIST", list)]));
+    if let Err(e) = result {
+        eprintln!("Warning: {}", e);
+    }
+}
+
+/// Run the `pre_sync` hook in the data directory
+pub fn before_sync(data_dir: &Path) -> Result<(), Error> {
+    let Some(command) = &hooks().pre_sync else {
+        return Ok(());
+    };
+    run("pre_sync", command, "", &[("UTODO_DATA_DIR", &data_dir.display().to_string())])?;
+    Ok(())
+}
+
+/// Run the `post_sync` hook with the outcome of the sync
+pub fn after_sync(data_dir: &Path, result: &Result<(), Error>) {
+    let Some(command) = &hooks().post_sync else {
+        return;
+    };
+    let outcome = if result.is_ok() { "ok" } else { "failed" };
+    let env = [
+        ("UTODO_DATA_DIR", data_dir.display().to_string()),
+        ("UTODO_SYNC_RESULT", outcome.to_string()),
+    ];
+    let env: Vec<(&str, &str)> = env.iter().map(|(key, value)| (*key, value.as_str())).collect();
+    if let Err(e) = run("post_sync", command, "", &env) {
+        eprintln!("Warning: {}", e);
+    }
+}
+
+/// Run a hook through `sh -c` with `input` on stdin, returning its stdout
+fn run(name: &str, command: &str, input: &str, env: &[(&str, &str)]) -> Result<String, Error> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("UTODO_EVENT", name)
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::Hook(format!("{} hook could not be started: {}", name, e)))?;
+
+    // Feed and drain the pipes on their own threads, so a hook ignoring them cannot block us
+    let mut stdin = child.stdin.take();
+    let input = input.to_string();
+    let writer = thread::spawn(move || {
+        if let Some(stdin) = stdin.as_mut() {
+            // A hook that does not read its input closes the pipe early, that is fine
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let timeout = Duration::from_secs(hooks().timeout_seconds);
+    // After a timeout processes started by the hook may still hold the pipes, so the
+    // threads are left behind instead of waiting for them
+    let Some(status) = wait_with_timeout(&mut child, timeout)? else {
+        return Err(Error::Hook(format!(
+            "{} hook timed out after {} seconds",
+            name,
+            timeout.as_secs()
+        )));
+    };
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    match status {
+        status if !status.success() => {
+            let reason = stderr.trim();
+            Err(Error::Hook(if reason.is_empty() {
+                format!("{} hook failed with {}", name, status)
+            } else {
+                format!("{} hook failed: {}", name, reason)
+            }))
+        }
+        _ => Ok(stdout),
+    }
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Wait for the hook to exit, killing it once `timeout` has passed
+pub(crate) fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+) -> io::Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
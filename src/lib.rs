@@ -0,0 +1,80 @@
+//! Library behind the `utodo` command line tool.
+//!
+//! Todos are stored one JSON object per line in named lists below the data
+//! directory from the [`Config`], or in a project-local `.utodo` directory.
+//! The library exposes the data model ([`Todo`], [`Priority`], workflow
+//! states), storage ([`TodoList`], archive and [`Trash`]), sorting
+//! ([`SortCriteria`]), filtering ([`Filter`], [`IdSelection`]) and git sync
+//! ([`GitRepo`]). Errors are reported as [`Error`].
+//!
+//! The workflow states and urgency weights come from the config unless they are
+//! passed in first with [`workflow::set_current`] and [`urgency::set_coefficients`].
+//! Reading the config never writes or prints anything, directories are created
+//! when something is saved. Saving and syncing print nothing to stdout: syncs
+//! that fail and are queued for later and failing `on_*` hooks are reported as
+//! warnings on stderr, and sync conflicts keep both versions unless
+//! [`sync::resolve_conflicts_interactively`] asks on stdin instead.
+//!
+//! ```no_run
+//! use unsafetodo::{Priority, SortCriteria, TodoBuilder, TodoList};
+//!
+//! # fn main() -> unsafetodo::Result<()> {
+//! let mut list = TodoList::load()?;
+//! list.add(
+//!     TodoBuilder::new()
+//!         .title("Write release notes")
+//!         .priority(Priority::High)
+//!         .build()?,
+//! );
+//! list.save()?;
+//!
+//! let open = unsafetodo::parse_filter("open")?;
+//! for todo in list.sorted(&[SortCriteria::Urgency]) {
+//!     if open.matches(&todo) {
+//!         println!("#{} {}", todo.get_id(), todo.get_title());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod config;
+pub mod duration;
+pub mod error;
+pub mod events;
+pub mod priority;
+pub mod reminder;
+pub mod scan;
+pub mod selection;
+pub mod sort_order;
+pub mod sync;
+pub mod sync_queue;
+pub mod time_tracking;
+pub mod todo;
+pub mod todo_list;
+pub mod trash;
+pub mod urgency;
+pub mod view;
+pub mod workflow;
+
+/// The `utodo` command line interface, used by the binary
+pub mod cli;
+
+mod agenda;
+mod alias;
+mod commands;
+mod display;
+mod editor;
+mod server;
+mod stats;
+
+pub use config::Config;
+pub use error::{Error, Result};
+pub use priority::Priority;
+pub use selection::{Filter, IdSelection, parse_filter, parse_id_selection};
+pub use sort_order::SortCriteria;
+pub use sync::GitRepo;
+pub use todo::{Todo, TodoBuilder};
+pub use todo_list::TodoList;
+pub use trash::Trash;
+pub use workflow::Workflow;
@@ -1,5 +1,6 @@
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Serialize, 
@@ -17,14 +18,6 @@ pub enum Priority {
 }
 
 impl Priority {
-    pub fn to_string(&self) -> String {
-        match self {
-            Priority::Low => String::from("Low"),
-            Priority::Medium => String::from("Medium"),
-            Priority::High => String::from("High"),
-        }
-    }
-
     pub fn priority_value(&self) -> u8 {
         match self {
             Priority::Low => 1,
@@ -34,6 +27,17 @@ impl Priority {
     }
 }
 
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+        };
+        f.pad(name)
+    }
+}
+
 // Implementierung von FromStr für Priority
 impl FromStr for Priority {
     type Err = String;
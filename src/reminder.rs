@@ -0,0 +1,276 @@
+use crate::config::{self, Config};
+use crate::error::Error;
+use crate::events;
+use crate::todo_list::TodoList;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+/// How long a webhook may take to connect and answer
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a notifier command may run before it is killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How reminders are delivered, set with `notifier` in the config
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Notifier {
+    /// Print reminders to stdout
+    #[default]
+    Stdout,
+    /// Run a shell command with the todo title as `$1` and the message as `$2`,
+    /// e.g. `notify-send "$1" "$2"`
+    Command { command: String },
+    /// POST each reminder as JSON to a plain HTTP endpoint, e.g. `http://127.0.0.1:9000/hook`
+    Webhook { url: String },
+}
+
+/// Why a todo asks for attention
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReminderKind {
+    /// The reminder time set on the todo has passed
+    At(DateTime<Utc>),
+    /// The todo is due today
+    DueToday(NaiveDate),
+    /// The due date has passed
+    Overdue(NaiveDate),
+}
+
+/// A reminder for one open todo
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub list: String,
+    pub id: u32,
+    pub title: String,
+    pub kind: ReminderKind,
+    /// Identifies the reminder across runs, changing the date arms it again
+    key: String,
+}
+
+impl Reminder {
+    pub fn message(&self) -> String {
+        let todo = if self.list == config::DEFAULT_LIST_NAME {
+            format!("#{}", self.id)
+        } else {
+            format!("{}:#{}", self.list, self.id)
+        };
+        match &self.kind {
+            ReminderKind::At(at) => format!(
+                "Reminder for {} set for {}",
+                todo,
+                at.with_timezone(&Local).format("%d.%m.%Y %H:%M")
+            ),
+            ReminderKind::DueToday(_) => format!("{} is due today", todo),
+            ReminderKind::Overdue(due) => {
+                format!("{} is overdue since {}", todo, due.format("%d.%m.%Y"))
+            }
+        }
+    }
+}
+
+/// Reminders of the open todos in all lists of `config` that are due at `now`
+pub fn due_reminders(config: &Config, now: DateTime<Utc>) -> Result<Vec<Reminder>, Error> {
+    let today = now.date_naive();
+    let mut reminders = Vec::new();
+    for list in config.list_names()? {
+        for todo in TodoList::load_from(config, &list)?.todos {
+            if todo.is_finished() {
+                continue;
+            }
+            let todo_key = format!("{}/{}", list, todo.get_creation_date().to_rfc3339());
+            let mut remind = |kind: ReminderKind, key: String| {
+                reminders.push(Reminder {
+                    list: list.clone(),
+                    id: todo.get_id(),
+                    title: todo.get_title(),
+                    kind,
+                    key: format!("{}/{}", todo_key, key),
+                });
+            };
+            if let Some(at) = todo.get_remind_at().filter(|at| *at <= now) {
+                remind(ReminderKind::At(at), format!("at:{}", at.to_rfc3339()));
+            }
+            // One reminder per due date, whether it is first noticed on the day or later
+            match todo.get_due_date() {
+                Some(due) if due == today => {
+                    remind(ReminderKind::DueToday(due), format!("due:{}", due));
+                }
+                Some(due) if due < today => {
+                    remind(ReminderKind::Overdue(due), format!("due:{}", due));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(reminders)
+}
+
+/// Reminders that were already sent, stored next to the lists
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SentReminders {
+    sent: BTreeMap<String, DateTime<Utc>>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl SentReminders {
+    pub fn load(config: &Config) -> Result<Self, Error> {
+        let path = config.storage_path().join(config::REMINDERS_FILE_NAME);
+        let mut sent = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| Error::corrupt(&path, e.line(), e))?
+        } else {
+            SentReminders::default()
+        };
+        sent.path = path;
+        Ok(sent)
+    }
+
+    /// Save the record, removing the file once it is empty
+    pub fn save(&self) -> Result<(), Error> {
+        if self.sent.is_empty() {
+            if self.path.exists() {
+                fs::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self).map_err(io::Error::from)?)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, reminder: &Reminder) -> bool {
+        self.sent.contains_key(&reminder.key)
+    }
+
+    pub fn record(&mut self, reminder: &Reminder, at: DateTime<Utc>) {
+        self.sent.insert(reminder.key.clone(), at);
+    }
+
+    /// Forget reminders that are no longer due, e.g. because the todo was finished
+    pub fn retain_due(&mut self, reminders: &[Reminder]) {
+        self.sent
+            .retain(|key, _| reminders.iter().any(|reminder| &reminder.key == key));
+    }
+}
+
+/// Send the reminders due at `now` that were not sent before, returning how many were sent.
+/// Reminders that fail to send are reported and retried on the next check.
+pub fn check(config: &Config, now: DateTime<Utc>) -> Result<usize, Error> {
+    let reminders = due_reminders(config, now)?;
+    let mut sent = SentReminders::load(config)?;
+    let mut count = 0;
+    for reminder in &reminders {
+        if sent.contains(reminder) {
+            continue;
+        }
+        match config.get_notifier().notify(reminder) {
+            Ok(()) => {
+                sent.record(reminder, now);
+                count += 1;
+            }
+            Err(e) => eprintln!("Failed to send reminder for '{}': {}", reminder.title, e),
+        }
+    }
+    sent.retain_due(&reminders);
+    sent.save()?;
+    Ok(count)
+}
+
+impl Notifier {
+    pub fn notify(&self, reminder: &Reminder) -> Result<(), Error> {
+        match self {
+            Notifier::Stdout => {
+                println!("{}: {}", reminder.title, reminder.message());
+                io::stdout().flush()?;
+                Ok(())
+            }
+            Notifier::Command { command } => {
+                let mut child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .arg("utodo")
+                    .arg(&reminder.title)
+                    .arg(reminder.message())
+                    .spawn()?;
+                let Some(status) = events::wait_with_timeout(&mut child, COMMAND_TIMEOUT)? else {
+                    return Err(Error::Io(io::Error::other(format!(
+                        "'{}' timed out after {} seconds",
+                        command,
+                        COMMAND_TIMEOUT.as_secs()
+                    ))));
+                };
+                if !status.success() {
+                    return Err(Error::Io(io::Error::other(format!(
+                        "'{}' exited with {}",
+                        command, status
+                    ))));
+                }
+                Ok(())
+            }
+            Notifier::Webhook { url } => post_json(url, &webhook_body(reminder)),
+        }
+    }
+}
+
+fn webhook_body(reminder: &Reminder) -> String {
+    let (kind, date) = match &reminder.kind {
+        ReminderKind::At(at) => ("remind_at", at.to_rfc3339()),
+        ReminderKind::DueToday(due) => ("due_today", due.to_string()),
+        ReminderKind::Overdue(due) => ("overdue", due.to_string()),
+    };
+    json!({
+        "list": reminder.list,
+        "id": reminder.id,
+        "title": reminder.title,
+        "kind": kind,
+        "date": date,
+        "message": reminder.message(),
+    })
+    .to_string()
+}
+
+/// POST `body` to a plain `http://host[:port]/path` URL, failing unless it answers with 2xx
+fn post_json(url: &str, body: &str) -> Result<(), Error> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| Error::Config(format!("Webhook URL '{}' has to start with http://", url)))?;
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+    let socket = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| Error::Config(format!("Cannot resolve webhook host '{}'", host)))?;
+
+    let mut stream = TcpStream::connect_timeout(&socket, WEBHOOK_TIMEOUT)?;
+    stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let status = response.split(' ').nth(1).unwrap_or_default();
+    if !status.starts_with('2') {
+        let status_line = response.lines().next().unwrap_or_default();
+        return Err(Error::Io(io::Error::other(format!(
+            "Webhook answered '{}'",
+            status_line
+        ))));
+    }
+    Ok(())
+}
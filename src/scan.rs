@@ -0,0 +1,219 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::events::Event;
+use crate::priority::Priority;
+use crate::todo::TodoBuilder;
+use crate::todo_list::TodoList;
+use ignore::WalkBuilder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where a todo imported from a source code comment was found
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    pub file: PathBuf,
+    pub line: usize,
+    /// Comment tag, e.g. TODO, FIXME or HACK
+    pub tag: String,
+    /// Comment text as found in the file, used to recognize it on re-scan
+    pub text: String,
+}
+
+impl SourceLocation {
+    /// `file:line`, relative to the working directory where possible
+    pub fn display(&self) -> String {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let file = self.file.strip_prefix(&cwd).unwrap_or(&self.file);
+        format!("{}:{}", file.display(), self.line)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScanSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub finished: usize,
+    /// Index of each changed todo with the event its hooks see
+    pub changes: Vec<(usize, Event)>,
+}
+
+/// Directory the source paths of imported todos are stored relative to, so lists shared
+/// through git point to the same files on every machine: the project of project-local
+/// todos, otherwise the home directory
+pub fn source_base(config: &Config) -> Option<PathBuf> {
+    let base = if config.is_local() {
+        config.storage_path().parent().map(Path::to_path_buf)
+    } else {
+        dirs::home_dir()
+    };
+    base.and_then(|base| base.canonicalize().ok())
+}
+
+/// `path` relative to `base`, or unchanged if it is not below it
+fn relative_to(path: &Path, base: Option<&Path>) -> PathBuf {
+    base.and_then(|base| path.strip_prefix(base).ok())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+/// Walk `root` respecting `.gitignore` and collect all TODO/FIXME/HACK comments
+pub fn scan_directory(root: &Path) -> Result<(Vec<SourceLocation>, usize), Error> {
+    let pattern = comment_pattern();
+    let mut found = Vec::new();
+    let mut files = 0;
+
+    for entry in WalkBuilder::new(root).require_git(false).build() {
+        let entry = entry.map_err(io::Error::other)?;
+        if !entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+            continue;
+        }
+        // Skip binary and non UTF-8 files
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        files += 1;
+        let file = entry.path().canonicalize()?;
+        found.extend(extract_comments(&pattern, &file, &content));
+    }
+
+    Ok((found, files))
+}
+
+fn comment_pattern() -> Regex {
+    // A comment marker (or a block comment continuation line) followed by the tag
+    Regex::new(
+        r"(?:(?://+|#+|/\*+|<!--|--|;+)|^\s*\*+)\s*\b(TODO|FIXME|HACK)\b(?:\([^)]*\))?:?\s*(.*?)\s*(?:\*/|-->)?\s*$",
+    )
+    .expect("invalid comment pattern")
+}
+
+fn extract_comments(pattern: &Regex, file: &Path, content: &str) -> Vec<SourceLocation> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let captures = pattern.captures(line)?;
+            Some(SourceLocation {
+                file: file.to_path_buf(),
+                line: i + 1,
+                tag: captures[1].to_string(),
+                text: captures[2].to_string(),
+            })
+        })
+        .collect()
+}
+
+fn title_for(location: &SourceLocation) -> String {
+    if location.text.is_empty() {
+        format!("{} in {}", location.tag, location.display())
+    } else {
+        location.text.clone()
+    }
+}
+
+fn priority_for(tag: &str) -> Priority {
+    match tag {
+        "FIXME" => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+/// Merge scanned comments into the list: update moved ones, add new ones
+/// and finish todos whose comment below `root` disappeared. Source paths are
+/// stored relative to `base`, see [`source_base`].
+pub fn apply_scan(
+    list: &mut TodoList,
+    base: Option<&Path>,
+    root: &Path,
+    found: Vec<SourceLocation>,
+) -> Result<ScanSummary, Error> {
+    let mut summary = ScanSummary::default();
+    let root = relative_to(root, base);
+    let found = found.into_iter().map(|location| SourceLocation {
+        file: relative_to(&location.file, base),
+        ..location
+    });
+    // Todos imported before paths were stored relative keep matching their comments
+    for todo in &mut list.todos {
+        if let Some(source) = todo.get_source().filter(|source| source.file.is_absolute()) {
+            let relative = SourceLocation {
+                file: relative_to(&source.file, base),
+                ..source.clone()
+            };
+            todo.set_source(relative);
+        }
+    }
+    let mut matched = vec![false; list.todos.len()];
+    let mut unmatched = Vec::new();
+
+    // Same file and text: the comment is still there, maybe on another line
+    for location in found {
+        let existing = list.todos.iter().enumerate().position(|(i, todo)| {
+            !matched[i]
+                && todo.get_source().is_some_and(|source| {
+                    source.file == location.file && source.text == location.text
+                })
+        });
+        match existing {
+            Some(i) => {
+                matched[i] = true;
+                let todo = &mut list.todos[i];
+                if todo.get_source() != Some(&location) || todo.is_finished() {
+                    todo.set_finished(false);
+                    todo.set_source(location);
+                    summary.updated += 1;
+                    summary.changes.push((i, Event::Update));
+                }
+            }
+            None => unmatched.push(location),
+        }
+    }
+
+    // Same file and line: the comment text was edited
+    for location in unmatched {
+        let existing = list.todos.iter().enumerate().position(|(i, todo)| {
+            !matched[i]
+                && !todo.is_finished()
+                && todo.get_source().is_some_and(|source| {
+                    source.file == location.file && source.line == location.line
+                })
+        });
+        match existing {
+            Some(i) => {
+                matched[i] = true;
+                let todo = &mut list.todos[i];
+                todo.set_title(title_for(&location));
+                todo.set_source(location);
+                summary.updated += 1;
+                summary.changes.push((i, Event::Update));
+            }
+            None => {
+                let todo = TodoBuilder::new()
+                    .title(title_for(&location))
+                    .priority(priority_for(&location.tag))
+                    .source(location)
+                    .build()?;
+                list.add(todo);
+                matched.push(true);
+                summary.added += 1;
+                summary.changes.push((list.todos.len() - 1, Event::Add));
+            }
+        }
+    }
+
+    for (i, todo) in list.todos.iter_mut().enumerate() {
+        let in_scanned_tree = todo
+            .get_source()
+            .is_some_and(|source| source.file.starts_with(&root));
+        if !matched[i] && in_scanned_tree && !todo.is_finished() {
+            todo.set_finished(true);
+            summary.finished += 1;
+            summary.changes.push((i, Event::Complete));
+        }
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,240 @@
+use crate::commands::parse_date_string;
+use crate::error::Error;
+use crate::priority::Priority;
+use crate::todo::Todo;
+use crate::todo_list::TodoList;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use clap::Args;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// IDs given as a comma separated list of single IDs and ranges, e.g. `1,4,7-9`.
+/// Ranges are kept as given and only expanded against an actual list.
+#[derive(Debug, Clone)]
+pub struct IdSelection(Vec<RangeInclusive<usize>>);
+
+impl IdSelection {
+    /// Sorted IDs of the selection in a list of `count` todos, or the lowest selected ID
+    /// the list does not have
+    pub fn resolve(&self, count: usize) -> Result<Vec<usize>, usize> {
+        let missing = self
+            .0
+            .iter()
+            .filter(|range| *range.end() >= count)
+            .map(|range| (*range.start()).max(count))
+            .min();
+        if let Some(missing) = missing {
+            return Err(missing);
+        }
+        let mut ids: Vec<usize> = self.0.iter().cloned().flatten().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+}
+
+/// Parse an ID selection given on the command line
+pub fn parse_id_selection(input: &str) -> Result<IdSelection, Error> {
+    let invalid = |part: &str| Error::Parse(format!("Invalid ID '{}', expected e.g. 1,4,7-9", part));
+    let mut ranges = Vec::new();
+    for part in input.split(',').map(str::trim) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start: usize = start.trim().parse().map_err(|_| invalid(part))?;
+                let end: usize = end.trim().parse().map_err(|_| invalid(part))?;
+                if start > end {
+                    return Err(Error::Parse(format!("Invalid range '{}', start is after end", part)));
+                }
+                ranges.push(start..=end);
+            }
+            None => {
+                let id = part.parse().map_err(|_| invalid(part))?;
+                ranges.push(id..=id);
+            }
+        }
+    }
+    Ok(IdSelection(ranges))
+}
+
+/// A single condition of a filter expression
+#[derive(Debug, Clone)]
+enum Condition {
+    Priority(Priority),
+    State(String),
+    Tag(String),
+    Due(NaiveDate),
+    FinishedSince(NaiveDate),
+    Finished,
+    Open,
+    Overdue,
+}
+
+impl Condition {
+    fn matches(&self, todo: &Todo) -> bool {
+        match self {
+            Condition::Priority(priority) => todo.get_priority() == priority,
+            Condition::State(state) => todo.get_state() == state,
+            Condition::Tag(tag) => todo.get_tags().contains(tag),
+            Condition::Due(date) => todo.get_due_date() == Some(*date),
+            Condition::FinishedSince(date) => {
+                todo.is_finished()
+                    && todo
+                        .get_finished_at()
+                        .is_some_and(|finished_at| finished_at.date_naive() >= *date)
+            }
+            Condition::Finished => todo.is_finished(),
+            Condition::Open => !todo.is_finished(),
+            Condition::Overdue => todo.is_overdue() && !todo.is_finished(),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("priority", value)) => Ok(Condition::Priority(value.parse().map_err(Error::Parse)?)),
+            Some(("state", value)) => Ok(Condition::State(value.to_string())),
+            Some(("tag", value)) => Ok(Condition::Tag(value.to_string())),
+            Some(("due", value)) => Ok(Condition::Due(parse_due(value)?)),
+            Some(("finished", value)) => Ok(Condition::FinishedSince(parse_since(value)?)),
+            None if s == "finished" => Ok(Condition::Finished),
+            None if s == "open" => Ok(Condition::Open),
+            None if s == "overdue" => Ok(Condition::Overdue),
+            _ => Err(Error::Parse(format!(
+                "Invalid condition '{}', expected priority:<p>, state:<s>, tag:<t>, due:<d>, finished[:<since>], open or overdue",
+                s
+            ))),
+        }
+    }
+}
+
+/// `today`, `tomorrow` or a date in the format dd.mm.YYYY
+fn parse_due(value: &str) -> Result<NaiveDate, Error> {
+    let today = Utc::now().date_naive();
+    match value {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today + Duration::days(1)),
+        date => parse_date_string(date),
+    }
+}
+
+/// `today`, `this-week` (since Monday) or a date in the format dd.mm.YYYY
+fn parse_since(value: &str) -> Result<NaiveDate, Error> {
+    let today = Utc::now().date_naive();
+    match value {
+        "today" => Ok(today),
+        "this-week" => Ok(today - Duration::days(today.weekday().num_days_from_monday() as i64)),
+        date => parse_date_string(date),
+    }
+}
+
+/// Conditions joined by `and`, each one optionally negated with `not`,
+/// e.g. `priority:low and not finished`
+#[derive(Debug, Clone)]
+pub struct Filter {
+    conditions: Vec<(bool, Condition)>,
+}
+
+impl Filter {
+    pub fn matches(&self, todo: &Todo) -> bool {
+        self.conditions
+            .iter()
+            .all(|(negated, condition)| condition.matches(todo) != *negated)
+    }
+}
+
+/// Parse a filter expression given with `--where`
+pub fn parse_filter(input: &str) -> Result<Filter, Error> {
+    let mut conditions = Vec::new();
+    let mut words = input.split_whitespace().peekable();
+    while words.peek().is_some() {
+        let mut negated = false;
+        let mut word = words.next();
+        if word == Some("not") {
+            negated = true;
+            word = words.next();
+        }
+        let word = word.ok_or_else(|| Error::Parse("Filter ends after 'not'".to_string()))?;
+        conditions.push((negated, word.parse()?));
+
+        match words.next() {
+            Some("and") | None => {}
+            Some(other) => {
+                return Err(Error::Parse(format!("Expected 'and' in filter, found '{}'", other)));
+            }
+        }
+    }
+    if conditions.is_empty() {
+        return Err(Error::Parse("Empty filter".to_string()));
+    }
+    Ok(Filter { conditions })
+}
+
+/// Filter and confirmation flags shared by the commands working on several todos
+#[derive(Args)]
+pub struct SelectionArgs {
+    /// Only todos matching the filter, e.g. "priority:low and finished"
+    #[arg(long = "where", value_parser = parse_filter)]
+    filter: Option<Filter>,
+
+    /// Apply to several todos without asking
+    #[arg(long, short)]
+    yes: bool,
+}
+
+impl SelectionArgs {
+    /// Indices of the todos given by `ids` and matching the filter
+    pub fn select(&self, todo_list: &TodoList, ids: Option<&IdSelection>) -> Result<Vec<usize>, Error> {
+        let indices = match ids {
+            Some(ids) => ids.resolve(todo_list.todos.len()).map_err(|missing| {
+                Error::NotFound(format!("Todo with ID {} not found", missing))
+            })?,
+            None if self.filter.is_some() => (0..todo_list.todos.len()).collect(),
+            None => {
+                return Err(Error::Parse(
+                    "Give the IDs of the todos or a filter with --where".to_string(),
+                ));
+            }
+        };
+
+        Ok(indices
+            .into_iter()
+            .filter(|index| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.matches(&todo_list.todos[*index]))
+            })
+            .collect())
+    }
+
+    /// Preview the selected todos and ask before changing more than one of them or
+    /// a filtered selection, returns whether to go ahead
+    pub fn confirm(&self, todo_list: &TodoList, indices: &[usize], action: &str) -> Result<bool, Error> {
+        if indices.is_empty() {
+            println!("No todos match");
+            return Ok(false);
+        }
+        if self.yes || (indices.len() == 1 && self.filter.is_none()) {
+            return Ok(true);
+        }
+
+        println!("{} {} todos:", action, indices.len());
+        for index in indices {
+            let todo = &todo_list.todos[*index];
+            println!("  #{} {}", todo.get_id(), todo.get_title());
+        }
+        print!("Continue? [y/N] ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let confirmed = matches!(input.trim().to_lowercase().as_str(), "y" | "yes");
+        if !confirmed {
+            println!("Aborted");
+        }
+        Ok(confirmed)
+    }
+}
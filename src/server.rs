@@ -0,0 +1,310 @@
+use crate::commands::{add, archive, complete, remove, update};
+use crate::config;
+use crate::duration::duration_from_minutes;
+use crate::error::Error;
+use crate::priority::Priority;
+use crate::selection::parse_filter;
+use crate::sort_order::SortCriteria;
+use crate::todo::{Todo, TodoBuilder};
+use crate::todo_list::TodoList;
+use crate::workflow;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+use std::net::IpAddr;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Fields a client may set when creating or updating a todo
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TodoInput {
+    title: Option<String>,
+    description: Option<String>,
+    priority: Option<Priority>,
+    due_date: Option<NaiveDate>,
+    state: Option<String>,
+    estimate_minutes: Option<i64>,
+    tags: Option<Vec<String>>,
+    remind_at: Option<DateTime<Utc>>,
+}
+
+/// Serve the todo API until the process is stopped. Requests are handled one at a
+/// time and each one loads and saves the list like a separate CLI invocation.
+///
+/// Without a `token` only requests naming a loopback host in their `Host` header are
+/// answered, which keeps web pages from reaching the API through DNS rebinding.
+/// Requests changing todos need a JSON content type, which browsers cannot send
+/// cross-origin without asking first.
+pub fn serve(bind: &str, token: Option<&str>) -> Result<(), Error> {
+    let server = Server::http(bind).map_err(|e| Error::Io(io::Error::other(e)))?;
+    let port = server.server_addr().to_ip().map(|addr| addr.port());
+    match server.server_addr().to_ip() {
+        Some(addr) => println!("Listening on http://{}", addr),
+        None => println!("Listening on {}", bind),
+    }
+    io::stdout().flush()?;
+
+    for mut request in server.incoming_requests() {
+        let (status, body) = if token.is_none() && !loopback_host(&request, port) {
+            (403, json!({ "error": "Requests must be addressed to a loopback host" }))
+        } else if !authorized(&request, token) {
+            (401, json!({ "error": "Missing or invalid bearer token" }))
+        } else if *request.method() != Method::Get && !json_content(&request) {
+            (415, json!({ "error": "Requests changing todos need 'Content-Type: application/json'" }))
+        } else {
+            let mut input = String::new();
+            match request.as_reader().read_to_string(&mut input) {
+                Ok(_) => handle(request.method(), request.url(), &input)
+                    .unwrap_or_else(|e| (status_code(&e), json!({ "error": e.to_string() }))),
+                Err(e) => (400, json!({ "error": e.to_string() })),
+            }
+        };
+        let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("static header is valid");
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type);
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn authorized(request: &Request, token: Option<&str>) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    header(request, "Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| given == token)
+}
+
+/// Whether the `Host` header names a loopback address on the port we listen on
+fn loopback_host(request: &Request, port: Option<u16>) -> bool {
+    let Some(host) = header(request, "Host") else {
+        return false;
+    };
+    let (name, given_port) = match host.rsplit_once(':') {
+        Some((name, given_port)) if !given_port.ends_with(']') => (name, given_port.parse().ok()),
+        _ => (host, Some(80)),
+    };
+    let loopback = name.eq_ignore_ascii_case("localhost")
+        || name
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    loopback && (port.is_none() || given_port == port)
+}
+
+fn json_content(request: &Request) -> bool {
+    header(request, "Content-Type")
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("application/json"))
+}
+
+fn status_code(error: &Error) -> u16 {
+    match error {
+        Error::NotFound(_) => 404,
+        Error::Parse(_) => 400,
+        Error::Config(_) | Error::Hook(_) | Error::Conflict(_) => 409,
+        _ => 500,
+    }
+}
+
+/// Answer a request, archiving finished todos after changes like the CLI does
+fn handle(method: &Method, url: &str, body: &str) -> Result<(u16, Value), Error> {
+    let response = route(method, url, body)?;
+    if *method != Method::Get
+        && let Err(e) = archive::auto_archive()
+    {
+        eprintln!("Warning: archiving finished todos failed: {}", e);
+    }
+    Ok(response)
+}
+
+fn route(method: &Method, url: &str, body: &str) -> Result<(u16, Value), Error> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["todos"]) => list_todos(query),
+        (Method::Post, ["todos"]) => create_todo(body),
+        (Method::Get, ["todos", id]) => {
+            let todo_list = TodoList::load()?;
+            let index = index_of(&todo_list, id)?;
+            Ok((200, json!(todo_list.todos[index])))
+        }
+        (Method::Patch, ["todos", id]) => update_todo(id, body),
+        (Method::Post, ["todos", id, "complete"]) => {
+            let mut todo_list = TodoList::load()?;
+            let index = index_of(&todo_list, id)?;
+            complete::complete_todos(&mut todo_list, &[index])?;
+            Ok((200, json!(todo_list.todos[index])))
+        }
+        (Method::Delete, ["todos", id]) => {
+            let mut todo_list = TodoList::load()?;
+            let index = index_of(&todo_list, id)?;
+            let removed = remove::remove_todos(&mut todo_list, &[index])?;
+            Ok((200, json!(removed[0].1)))
+        }
+        _ => Err(Error::NotFound(format!("No route for {} {}", method, path))),
+    }
+}
+
+/// `GET /todos?where=<filter>&sort=<criteria,...>`, sorted like `show` by default
+fn list_todos(query: &str) -> Result<(u16, Value), Error> {
+    let todo_list = TodoList::load()?;
+    let mut filter = None;
+    let mut sort_order = config::load_config()?.get_sort_order().clone();
+    for (key, value) in query_pairs(query) {
+        match key.as_str() {
+            "where" => filter = Some(parse_filter(&value)?),
+            "sort" => {
+                sort_order = value
+                    .split(',')
+                    .map(|criteria| SortCriteria::from_str(criteria, true))
+                    .collect::<Result<_, _>>()
+                    .map_err(Error::Parse)?;
+            }
+            other => return Err(Error::Parse(format!("Unknown query parameter '{}'", other))),
+        }
+    }
+
+    let todos: Vec<Todo> = todo_list
+        .sorted(&sort_order)
+        .into_iter()
+        .filter(|todo| filter.as_ref().is_none_or(|filter| filter.matches(todo)))
+        .collect();
+    Ok((200, json!(todos)))
+}
+
+fn create_todo(body: &str) -> Result<(u16, Value), Error> {
+    let input = parse_input(body)?;
+    let mut todo_list = TodoList::load()?;
+    let todo = TodoBuilder::new()
+        .title(input.title.clone().unwrap_or_default())
+        .description(input.description.clone())
+        .priority(input.priority.clone())
+        .due_date(input.due_date)
+        .state(input.state.clone())
+        .estimate(input.estimate_minutes.map(estimate).transpose()?)
+        .tags(input.tags.clone().unwrap_or_default())
+        .remind_at(input.remind_at)
+        .build()?;
+    let todo = add::add_todo(&mut todo_list, todo)?;
+    Ok((201, json!(todo)))
+}
+
+fn update_todo(id: &str, body: &str) -> Result<(u16, Value), Error> {
+    let input = parse_input(body)?;
+    let mut todo_list = TodoList::load()?;
+    let index = index_of(&todo_list, id)?;
+    if let Some(state) = &input.state
+        && !workflow::current().contains(state)
+    {
+        return Err(Error::Parse(format!("Unknown workflow state '{}'", state)));
+    }
+    let estimate = input.estimate_minutes.map(estimate).transpose()?;
+
+    update::change_todos(&mut todo_list, &[index], |todo| {
+        if let Some(title) = &input.title {
+            todo.set_title(title.clone());
+        }
+        if let Some(description) = &input.description {
+            todo.set_description(description.clone());
+        }
+        if let Some(priority) = &input.priority {
+            todo.set_priority(priority.clone());
+        }
+        if let Some(due_date) = input.due_date {
+            todo.set_due_date(due_date);
+        }
+        if let Some(state) = &input.state {
+            todo.set_state(state.clone());
+        }
+        if let Some(estimate) = estimate {
+            todo.set_estimate(estimate);
+        }
+        if let Some(tags) = &input.tags {
+            todo.set_tags(tags.clone());
+        }
+        if let Some(remind_at) = input.remind_at {
+            todo.set_remind_at(remind_at);
+        }
+    })?;
+    Ok((200, json!(todo_list.todos[index])))
+}
+
+fn estimate(minutes: i64) -> Result<Duration, Error> {
+    duration_from_minutes(minutes).ok_or_else(|| {
+        Error::Parse(format!(
+            "Invalid estimate_minutes {}: use a positive number of minutes up to a million hours",
+            minutes
+        ))
+    })
+}
+
+fn parse_input(body: &str) -> Result<TodoInput, Error> {
+    if body.trim().is_empty() {
+        return Ok(TodoInput::default());
+    }
+    serde_json::from_str(body).map_err(|e| Error::Parse(format!("Invalid request body: {}", e)))
+}
+
+fn index_of(todo_list: &TodoList, id: &str) -> Result<usize, Error> {
+    let index: usize = id
+        .parse()
+        .map_err(|_| Error::Parse(format!("Invalid todo ID '{}'", id)))?;
+    if todo_list.get_todo(index).is_none() {
+        return Err(Error::NotFound(format!("Todo with ID {} not found", index)));
+    }
+    Ok(index)
+}
+
+/// Decoded `key=value` pairs of a query string
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
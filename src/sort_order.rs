@@ -10,6 +10,9 @@ pub enum SortCriteria {
     CreatedAsc,
     DueDate,
     DueDateReverse,
+    Estimate,
+    EstimateReverse,
+    Urgency,
     TitleAsc,
     TitleDesc,
     Status,
@@ -0,0 +1,121 @@
+use crate::priority::Priority;
+use crate::todo::Todo;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+/// Number of weeks shown in the completion histogram when no `since` is given
+const DEFAULT_WEEKS: i64 = 12;
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Serialize, Debug, Default)]
+pub struct PriorityCounts {
+    pub low: usize,
+    pub medium: usize,
+    pub high: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WeekCount {
+    /// ISO week, e.g. 2025-W07
+    pub week: String,
+    pub completed: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Stats {
+    pub total: usize,
+    pub open: usize,
+    pub finished: usize,
+    pub overdue: usize,
+    /// Open todos per priority
+    pub open_by_priority: PriorityCounts,
+    /// Average age of open todos in days, none if nothing is open
+    pub average_open_age_days: Option<f64>,
+    pub completions_per_week: Vec<WeekCount>,
+}
+
+impl Stats {
+    /// Compute statistics for todos created between `since` and `until` (inclusive).
+    /// Completions are counted by the date they were finished.
+    pub fn compute(
+        todos: &[Todo],
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+        now: DateTime<Utc>,
+    ) -> Stats {
+        let today = now.date_naive();
+        let in_range = |date: NaiveDate| {
+            since.is_none_or(|since| date >= since) && until.is_none_or(|until| date <= until)
+        };
+
+        let created: Vec<&Todo> = todos
+            .iter()
+            .filter(|todo| in_range(todo.get_creation_date().date_naive()))
+            .collect();
+        let open: Vec<&Todo> = created.iter().copied().filter(|todo| !todo.is_finished()).collect();
+
+        let mut open_by_priority = PriorityCounts::default();
+        for todo in &open {
+            match todo.get_priority() {
+                Priority::Low => open_by_priority.low += 1,
+                Priority::Medium => open_by_priority.medium += 1,
+                Priority::High => open_by_priority.high += 1,
+            }
+        }
+
+        let average_open_age_days = (!open.is_empty()).then(|| {
+            let total_hours: i64 = open
+                .iter()
+                .map(|todo| (now - todo.get_creation_date()).num_hours())
+                .sum();
+            total_hours as f64 / 24.0 / open.len() as f64
+        });
+
+        // Histogram from the first to the last week of the range
+        let last = until.unwrap_or(today).min(today);
+        let first = since.unwrap_or(last - Duration::weeks(DEFAULT_WEEKS - 1));
+        let mut completions_per_week = Vec::new();
+        let mut week_start = week_monday(first);
+        while week_start <= last {
+            let week_end = week_start + Duration::days(6);
+            let completed = todos
+                .iter()
+                .filter_map(|todo| todo.get_finished_at())
+                .map(|finished_at| finished_at.date_naive())
+                .filter(|date| *date >= week_start && *date <= week_end && in_range(*date))
+                .count();
+            let iso = week_start.iso_week();
+            completions_per_week.push(WeekCount {
+                week: format!("{}-W{:02}", iso.year(), iso.week()),
+                completed,
+            });
+            week_start += Duration::weeks(1);
+        }
+
+        Stats {
+            total: created.len(),
+            open: open.len(),
+            finished: created.len() - open.len(),
+            overdue: open.iter().filter(|todo| todo.is_overdue()).count(),
+            open_by_priority,
+            average_open_age_days,
+            completions_per_week,
+        }
+    }
+}
+
+fn week_monday(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Render values as a one-line sparkline, scaled to the largest value
+pub fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| {
+            let level = (value * (SPARK_CHARS.len() - 1)).checked_div(max).unwrap_or(0);
+            SPARK_CHARS[level]
+        })
+        .collect()
+}
@@ -2,10 +2,25 @@ use std::fs;
 use std::io::{self};
 use std::path::PathBuf;
 use std::process::{Command, Output};
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::display::display_todo_vector;
 use crate::config;
+use crate::error::Error;
+use crate::events;
+use crate::sync_queue::SyncQueue;
+use crate::trash::Trash;
 
 use crate::todo_list;
+
+/// Set by the command line tool when someone can answer prompts
+static INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Ask on stdin which version to keep when a sync runs into merge conflicts. Otherwise
+/// both versions are kept without printing anything.
+pub fn resolve_conflicts_interactively() {
+    INTERACTIVE.store(true, Ordering::Relaxed);
+}
+
 pub struct GitRepo {
     path: PathBuf,
 }
@@ -18,35 +33,99 @@ impl GitRepo {
     }
 
     fn run_git_command(&self, args: &[&str]) -> io::Result<()> {
+        self.git_output(args).map(|_| ())
+    }
+
+    /// Run a git command in the repository and return its stdout
+    fn git_output(&self, args: &[&str]) -> io::Result<String> {
         let output: Output = Command::new("git")
             .arg("-C")
             .arg(&self.path)
             .args(args)
             .output()?;
 
-        //zum debuggen
         if !output.status.success() {
-            //eprintln!("Git error: git {}", args.join(" "));
-            //eprintln!("stdout:\n{}", String::from_utf8_lossy(&output.stdout));
-            //eprintln!("stderr:\n{}", String::from_utf8_lossy(&output.stderr));
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                stderr.trim()
+            )));
         }
 
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Directory git runs hooks from, honoring `core.hooksPath`
+    pub fn hooks_dir(&self) -> io::Result<PathBuf> {
+        let hooks = self.git_output(&["rev-parse", "--git-path", "hooks"])?;
+        Ok(self.path.join(hooks.trim()))
+    }
+
+    /// Hash and full message of the commit HEAD points to
+    pub fn head_commit(&self) -> io::Result<(String, String)> {
+        let output = self.git_output(&["log", "-1", "--format=%H%n%B"])?;
+        let (hash, message) = output.split_once('\n').unwrap_or((output.as_str(), ""));
+        Ok((hash.trim().to_string(), message.to_string()))
+    }
+
+    /// Check whether the index differs from HEAD or a merge is waiting to be concluded
+    fn has_staged_changes(&self) -> io::Result<bool> {
+        if self.path.join(".git").join("MERGE_HEAD").exists() {
+            return Ok(true);
+        }
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["diff", "--cached", "--quiet"])
+            .status()?;
+        Ok(!status.success())
     }
 
     pub fn handle_merge_conflict(&self) -> io::Result<()> {
-        let path = self.path.join(config::TODO_FILE_NAME);
-        let file = fs::read_to_string(&path)?;
+        let interactive = INTERACTIVE.load(Ordering::Relaxed);
+        for conflicted in self.conflicted_files()? {
+            if interactive {
+                println!("\nResolving conflicts in {}", conflicted);
+            }
+            let path = self.path.join(&conflicted);
+            // The trash is one JSON document, not a todo per line, so keep the entries of both sides
+            if conflicted == config::TRASH_FILE_NAME {
+                fs::write(&path, self.merge_trash(&conflicted)?)?;
+                continue;
+            }
+            let file = fs::read_to_string(&path)?;
 
-        let resolved_lines = self.resolve_conflicts(file)?;
+            let resolved_lines = self.resolve_conflicts(file)?;
 
-        // Overwrite file with resolved content
-        fs::write(&path, resolved_lines.join("\n"))?;
+            // Overwrite file with resolved content
+            fs::write(&path, resolved_lines.join("\n"))?;
+        }
 
-        println!("Merge conflicts resolved. You can now commit the changes.");
+        if interactive {
+            println!("Merge conflicts resolved. You can now commit the changes.");
+        }
         Ok(())
     }
 
+    /// Merge our and their version of the conflicted trash, a side that deleted it counts as empty
+    fn merge_trash(&self, file: &str) -> io::Result<String> {
+        let ours = self.git_output(&["show", &format!(":2:{}", file)]).unwrap_or_default();
+        let theirs = self.git_output(&["show", &format!(":3:{}", file)]).unwrap_or_default();
+        Ok(Trash::merge_files(&ours, &theirs)?)
+    }
+
+    /// Files with unresolved merge conflicts, relative to the repository
+    fn conflicted_files(&self) -> io::Result<Vec<String>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["diff", "--name-only", "--diff-filter=U"])
+            .output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().map(|line| line.to_string()).collect())
+    }
+
     fn resolve_conflicts(&self, content: String) -> io::Result<Vec<String>> {
         let mut result = Vec::new();
         let mut lines = content.lines();
@@ -57,7 +136,7 @@ impl GitRepo {
                 let mut incoming_block = Vec::new();
 
                 // Collect HEAD section
-                while let Some(l) = lines.next() {
+                for l in lines.by_ref() {
                     if l.starts_with("=======") {
                         break;
                     }
@@ -65,7 +144,7 @@ impl GitRepo {
                 }
 
                 // Collect incoming section
-                while let Some(l) = lines.next() {
+                for l in lines.by_ref() {
                     if l.starts_with(">>>>>>>") {
                         break;
                     }
@@ -85,6 +164,12 @@ impl GitRepo {
                 //    println!("    {}", l);
                 //}
 
+                if !INTERACTIVE.load(Ordering::Relaxed) {
+                    result.extend(head_block);
+                    result.extend(incoming_block);
+                    continue;
+                }
+
                 let local_todos = todo_list::todos_from_json_lines(&head_block);
                 let incoming_todos = todo_list::todos_from_json_lines(&incoming_block);
 
@@ -124,7 +209,7 @@ impl GitRepo {
         let branch = self.get_current_branch()?;
 
         let tracking_status = Command::new("git")
-            .args(&["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"])
+            .args(["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"])
             .current_dir(&self.path)
             .output();
 
@@ -138,7 +223,7 @@ impl GitRepo {
         if needs_tracking {
             // Tracking fehlt → setze Upstream auf origin/main
             let remote_refs = Command::new("git")
-                .args(&["ls-remote", "--heads", "origin"])
+                .args(["ls-remote", "--heads", "origin"])
                 .current_dir(&self.path)
                 .output()?;
 
@@ -148,11 +233,11 @@ impl GitRepo {
             } else if output.contains("refs/heads/master") {
                 "master"
             } else {
-                return Err(io::Error::new(io::ErrorKind::Other, "No known default branch found"));
+                return Err(io::Error::other("No known default branch found"));
             };
 
             let set_upstream = Command::new("git")
-                .args(&[
+                .args([
                     "branch",
                     "--set-upstream-to",
                     &format!("origin/{}", remote_branch),
@@ -162,9 +247,7 @@ impl GitRepo {
                 .status()?;
 
             if !set_upstream.success() {
-                return Err(io::Error::new(io::ErrorKind::Other, "Failed to set upstream tracking"));
-            } else {
-                println!("Tracking set: {} → origin/{}", branch, remote_branch);
+                return Err(io::Error::other("Failed to set upstream tracking"));
             }
         }
 
@@ -173,14 +256,12 @@ impl GitRepo {
 
 
     pub fn pull(&self) -> io::Result<()> {
-        println!("Pulling changes from origin...");
-
         self.ensure_tracking_branch()?; // 🔧 Tracking sicherstellen
 
         let output = Command::new("git")
             .arg("-C")
             .arg(&self.path)
-            .args(&["pull", "--no-rebase", "--allow-unrelated-histories"])
+            .args(["pull", "--no-rebase", "--allow-unrelated-histories"])
             .output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -192,43 +273,42 @@ impl GitRepo {
             //println!("Pull erfolgreich.");
             Ok(())
         } else {
-            eprintln!("Git pull stderr:\n{}", stderr);
-
             let conflict_detected = stdout.contains("CONFLICT")
                 || stderr.contains("CONFLICT")
                 || stdout.contains("Automatic merge failed")
                 || stderr.contains("Automatic merge failed");
 
             if conflict_detected {
-                eprintln!("Merge-Conflict detected.");
                 self.handle_merge_conflict()?;
                 Ok(())
             } else {
-                eprintln!("Git pull Error:\n{}", stderr);
-                Err(io::Error::new(io::ErrorKind::Other, "Git pull failed"))
+                Err(io::Error::other(format!("git pull failed: {}", stderr.trim())))
             }
         }
     }
 
-    pub fn add(&self, file: &str) -> io::Result<()> {
-        self.run_git_command(&["add", file])
+    /// Stage additions, modifications and deletions below the given paths
+    pub fn add(&self, paths: &[&str]) -> io::Result<()> {
+        let mut args = vec!["add", "-A", "--"];
+        args.extend_from_slice(paths);
+        self.run_git_command(&args)
     }
 
     pub fn commit(&self, message: &str) -> io::Result<()> {
+        if !self.has_staged_changes()? {
+            return Ok(());
+        }
         self.run_git_command(&["commit", "-m", message])
     }
 
     fn get_current_branch(&self) -> io::Result<String> {
         let output = Command::new("git")
-            .args(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
             .current_dir(&self.path)
             .output()?;
 
         if !output.status.success() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "Failed to get current branch",
-            ));
+            return Err(io::Error::other("Failed to get current branch"));
         }
 
         let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -236,23 +316,75 @@ impl GitRepo {
     }
 
     pub fn push(&self) -> io::Result<()> {
-        println!("Pushing changes to origin...");
-
         let branch = self.get_current_branch()?;
         self.run_git_command(&["push", "-u", "origin", &branch])
     }
 
 
-    pub fn sync_file(&self, file: &str) -> io::Result<()> {
-        self.add(file)?;
+    pub fn sync_files(&self, paths: &[&str]) -> io::Result<()> {
+        self.add(paths)?;
         self.commit("Update todo list manually?")?;
         self.pull()?;
-        self.add(file)?;
+        self.add(paths)?;
         self.commit("Update todo list")?;
         self.push()?;
         Ok(())
     }
 
+    /// Todo storage paths that exist in the repository
+    fn todo_paths(&self) -> Vec<&'static str> {
+        [config::TODO_FILE_NAME, config::LISTS_DIR_NAME, config::ARCHIVE_DIR_NAME, config::TRASH_FILE_NAME]
+            .into_iter()
+            .filter(|path| self.path.join(path).exists())
+            .collect()
+    }
+
+    /// Sync the default list and all named lists
+    pub fn sync_todos(&self) -> io::Result<()> {
+        self.sync_files(&self.todo_paths())
+    }
+
+    /// Sync all lists between the `pre_sync` and `post_sync` hooks
+    fn sync_todos_with_hooks(&self) -> Result<(), Error> {
+        let result = events::before_sync(&self.path)
+            .and_then(|()| self.sync_todos().map_err(|e| Error::Sync(e.to_string())));
+        events::after_sync(&self.path, &result);
+        result
+    }
+
+    /// Sync all lists and record the attempt in the sync queue if it fails
+    pub fn sync_todos_queued(&self) -> Result<(), Error> {
+        let mut queue = SyncQueue::load(&self.path)?;
+        match self.sync_todos_with_hooks() {
+            Ok(()) => {
+                if !queue.is_empty() {
+                    queue.clear();
+                    queue.save(&self.path)?;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                queue.record_failure(&e.to_string());
+                queue.save(&self.path)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Retry syncing all lists if earlier attempts are still pending, returning how many
+    /// pending changes were synced
+    pub fn retry_pending(&self) -> Result<usize, Error> {
+        let mut queue = SyncQueue::load(&self.path)?;
+        if queue.is_empty() {
+            return Ok(0);
+        }
+        let pending = queue.len();
+        self.sync_todos_with_hooks()?;
+        queue.clear();
+        queue.save(&self.path)?;
+        Ok(pending)
+    }
+
     pub fn setup(&self, remote_url: Option<&str>) -> io::Result<()> {
         let git_dir = self.path.join(".git");
 
@@ -261,21 +393,14 @@ impl GitRepo {
 
             // Prüfen, ob ein Remote vorhanden ist
             let output = Command::new("git")
-                .args(&["remote", "get-url", "origin"])
+                .args(["remote", "get-url", "origin"])
                 .current_dir(&self.path)
                 .output()?;
 
             if !output.status.success() {
                 // Kein origin vorhanden, aber remote_url wurde übergeben
                 if let Some(remote) = remote_url {
-                    println!("Setting remote origin to: {}", remote);
-                    let status = Command::new("git")
-                        .args(&["remote", "add", "origin", remote])
-                        .current_dir(&self.path)
-                        .status()?;
-                    if !status.success() {
-                        eprintln!("Failed to add remote origin.");
-                    }
+                    self.run_git_command(&["remote", "add", "origin", remote])?;
                 }
             }
 
@@ -283,23 +408,19 @@ impl GitRepo {
         }
 
         if let Some(remote) = remote_url {
-            println!("Cloning from remote repository: {}", remote);
-            let status = Command::new("git")
-                .args(&["clone", remote, self.path.to_str().unwrap()])
-                .status()?;
-            if !status.success() {
-                eprintln!("Failed to clone repository.");
+            let output = Command::new("git")
+                .args(["clone", remote])
+                .arg(&self.path)
+                .output()?;
+            if !output.status.success() {
+                return Err(io::Error::other(format!(
+                    "git clone failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
             }
         } else {
-            println!("Initializing new local Git repository...");
             fs::create_dir_all(&self.path)?;
-            let status = Command::new("git")
-                .args(&["init"])
-                .current_dir(&self.path)
-                .status()?;
-            if !status.success() {
-                eprintln!("Failed to initialize repository.");
-            }
+            self.run_git_command(&["init"])?;
 
             let todos_path = self.path.join(config::TODO_FILE_NAME);
             if !todos_path.exists() {
@@ -307,48 +428,34 @@ impl GitRepo {
             }
 
             Command::new("git")
-                .args(&["add", config::TODO_FILE_NAME])
+                .args(["add", config::TODO_FILE_NAME])
                 .current_dir(&self.path)
-                .status()?;
+                .output()?;
             Command::new("git")
-                .args(&["commit", "-m", "Initial commit"])
+                .args(["commit", "-m", "Initial commit"])
                 .current_dir(&self.path)
-                .status()?;
+                .output()?;
 
             if let Some(remote) = remote_url {
-                println!("Adding remote origin: {}", remote);
-                let status = Command::new("git")
-                    .args(&["remote", "add", "origin", remote])
-                    .current_dir(&self.path)
-                    .status()?;
-                if !status.success() {
-                    eprintln!("Failed to add remote origin.");
-                } else {
-                    // Remote hinzugefügt, jetzt den default branch ermitteln
-                    if let Ok(default_branch) = self.get_remote_default_branch() {
-                        println!("Remote default branch is '{}'", &default_branch);
-
-                        // Tracking-Branch setzen
-                        let branch_output = Command::new("git")
-                            .args(&["branch", "--show-current"])
-                            .current_dir(&self.path)
-                            .output()?;
-                        let branch_output_str = String::from_utf8_lossy(&branch_output.stdout);
-                        let current_branch = branch_output_str.trim();
-
-                        if current_branch == default_branch {
-                            let remote_branch = format!("origin/{}", default_branch);
-                            let track_status = Command::new("git")
-                                .args(&["branch", "--set-upstream-to", &remote_branch, &default_branch])
-                                .current_dir(&self.path)
-                                .status()?;
-
-                            if !track_status.success() {
-                                eprintln!("Failed to set upstream branch.");
-                            } else {
-                                println!("Tracking branch set to origin/{}.", default_branch);
-                            }
-                        }
+                self.run_git_command(&["remote", "add", "origin", remote])?;
+                // Remote hinzugefügt, jetzt den default branch ermitteln
+                if let Ok(default_branch) = self.get_remote_default_branch() {
+                    // Tracking-Branch setzen
+                    let branch_output = Command::new("git")
+                        .args(["branch", "--show-current"])
+                        .current_dir(&self.path)
+                        .output()?;
+                    let branch_output_str = String::from_utf8_lossy(&branch_output.stdout);
+                    let current_branch = branch_output_str.trim();
+
+                    if current_branch == default_branch {
+                        let remote_branch = format!("origin/{}", default_branch);
+                        self.run_git_command(&[
+                            "branch",
+                            "--set-upstream-to",
+                            &remote_branch,
+                            &default_branch,
+                        ])?;
                     }
                 }
             }
@@ -359,11 +466,11 @@ impl GitRepo {
 
     fn get_remote_default_branch(&self) -> io::Result<String> {
         let output = Command::new("git")
-            .args(&["symbolic-ref", "refs/remotes/origin/HEAD"])
+            .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
             .current_dir(&self.path)
             .output()?;
         if !output.status.success() {
-            return Err(io::Error::new(io::ErrorKind::Other, "Failed to get remote HEAD"));
+            return Err(io::Error::other("Failed to get remote HEAD"));
         }
         let refname = String::from_utf8_lossy(&output.stdout).trim().to_string();
         // refname ist z.B. "refs/remotes/origin/main"
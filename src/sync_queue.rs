@@ -0,0 +1,74 @@
+use crate::config;
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A sync attempt that could not reach the remote
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingSync {
+    failed_at: DateTime<Utc>,
+    error: String,
+}
+
+/// Failed syncs waiting to be pushed, stored next to the todo file
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SyncQueue {
+    pending: Vec<PendingSync>,
+}
+
+impl SyncQueue {
+    fn file_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(config::SYNC_QUEUE_FILE_NAME)
+    }
+
+    /// Load the queue from the data directory, empty if there is none
+    pub fn load(data_dir: &Path) -> Result<Self, Error> {
+        let path = Self::file_path(data_dir);
+        if !path.exists() {
+            return Ok(SyncQueue::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| Error::corrupt(&path, e.line(), e))
+    }
+
+    /// Save the queue, removing the file once nothing is pending
+    pub fn save(&self, data_dir: &Path) -> Result<(), Error> {
+        let path = Self::file_path(data_dir);
+        if self.pending.is_empty() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        fs::write(path, serde_json::to_string_pretty(self).map_err(io::Error::from)?)?;
+        Ok(())
+    }
+
+    pub fn record_failure(&mut self, error: &str) {
+        self.pending.push(PendingSync {
+            failed_at: Utc::now(),
+            error: error.to_string(),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Number of changes that were saved locally but not pushed yet
+pub fn pending_count() -> Result<usize, Error> {
+    let data_dir = config::get_data_dir()?;
+    Ok(SyncQueue::load(&data_dir)?.len())
+}
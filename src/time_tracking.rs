@@ -0,0 +1,46 @@
+use crate::config;
+use crate::error::Error;
+use crate::todo_list::TodoList;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Time spent on a todo, running while `end` is not set
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeEntry {
+    pub fn running(start: DateTime<Utc>) -> Self {
+        TimeEntry { start, end: None }
+    }
+
+    pub fn finished(start: DateTime<Utc>, duration: Duration) -> Self {
+        TimeEntry {
+            start,
+            end: Some(start + duration),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.end.is_none()
+    }
+
+    /// Tracked time, running entries count up to `now`
+    pub fn duration(&self, now: DateTime<Utc>) -> Duration {
+        self.end.unwrap_or(now) - self.start
+    }
+}
+
+/// Find the list and position of the todo with the running timer, searching all lists
+pub fn find_running_timer() -> Result<Option<(TodoList, usize)>, Error> {
+    let config = config::load_config()?;
+    for name in config.list_names()? {
+        let list = TodoList::load_from(&config, &name)?;
+        if let Some(index) = list.todos.iter().position(|todo| todo.has_running_timer()) {
+            return Ok(Some((list, index)));
+        }
+    }
+    Ok(None)
+}
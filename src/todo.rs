@@ -1,20 +1,49 @@
-use crate::config;
+use crate::error::Error;
 use crate::priority::Priority;
+use crate::scan::SourceLocation;
 use crate::sort_order::SortCriteria;
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::time_tracking::TimeEntry;
+use crate::urgency;
+use crate::workflow;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::io::{self, Write};
+use std::path::Path;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Todo {
     id: u32,
     title: String,
     description: Option<String>,
+    /// Mirrors whether `state` is the final workflow state, kept so older versions can read the file
     finished: bool,
+    /// Current workflow state, missing in files written before workflows existed
+    #[serde(default)]
+    state: String,
     priority: Priority,
     created_at: DateTime<Utc>,
     due_date: Option<NaiveDate>,
+    /// Source code comment this todo was imported from by `scan`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<SourceLocation>,
+    /// Hashes of commits referencing this todo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    commits: Vec<String>,
+    /// When the todo was last marked as finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finished_at: Option<DateTime<Utc>>,
+    /// Time tracked with `start`/`stop` and `log`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    time_entries: Vec<TimeEntry>,
+    /// Estimated effort in minutes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimate_minutes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// When `remind` should send a reminder, in addition to the one for the due date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remind_at: Option<DateTime<Utc>>,
 }
 
 impl Todo {
@@ -24,9 +53,17 @@ impl Todo {
             title,
             description: None,
             finished: false,
+            state: workflow::current().initial().to_string(),
             priority: Priority::Low,
             created_at: Utc::now(),
             due_date: None,
+            source: None,
+            commits: Vec::new(),
+            finished_at: None,
+            time_entries: Vec::new(),
+            estimate_minutes: None,
+            tags: Vec::new(),
+            remind_at: None,
         }
     }
 
@@ -34,25 +71,47 @@ impl Todo {
         let mut todo: Todo = serde_json::from_str(line)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
         todo.set_id(id);
+        if todo.state.is_empty() {
+            let workflow = workflow::current();
+            let state = if todo.finished { workflow.done() } else { workflow.initial() };
+            todo.state = state.to_string();
+        }
         Ok(todo)
     }
 
-    pub fn save_to_file(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let as_json = serde_json::to_string(self)?;
-        let path = config::get_data_path();
+    pub fn save_to_file(&self, path: &Path) -> Result<(), Error> {
+        let as_json = serde_json::to_string(self).map_err(io::Error::from)?;
 
         let mut file = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
-            .open(&path)?;
+            .open(path)?;
 
         writeln!(file, "{}", as_json)?;
 
         Ok(())
     }
 
+    /// Whether the todo is in the final workflow state. Todos in states the workflow no longer
+    /// has keep the finished flag they were stored with.
     pub fn is_finished(&self) -> bool {
-        self.finished
+        let workflow = workflow::current();
+        if workflow.contains(&self.state) {
+            workflow.is_done(&self.state)
+        } else {
+            self.finished
+        }
+    }
+
+    /// Position of the state in the workflow. Unknown states count as the initial state,
+    /// or as the final one for finished todos.
+    pub fn workflow_position(&self) -> usize {
+        let workflow = workflow::current();
+        if !workflow.contains(&self.state) && self.finished {
+            workflow.states().len() - 1
+        } else {
+            workflow.position(&self.state)
+        }
     }
 
     pub fn is_overdue(&self) -> bool {
@@ -65,7 +124,7 @@ impl Todo {
     }
 
     pub fn complete(&mut self, complete: bool) {
-        self.finished = complete;
+        self.set_finished(complete);
     }
 
     pub fn get_title(&self) -> String {
@@ -93,7 +152,57 @@ impl Todo {
     }
 
     pub fn get_finished(&self) -> bool {
-        self.finished
+        self.is_finished()
+    }
+
+    pub fn get_state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn get_source(&self) -> Option<&SourceLocation> {
+        self.source.as_ref()
+    }
+
+    pub fn get_finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Urgency score based on the configured coefficients
+    pub fn urgency(&self) -> f64 {
+        urgency::coefficients().score(self, Utc::now().date_naive())
+    }
+
+    pub fn get_remind_at(&self) -> Option<DateTime<Utc>> {
+        self.remind_at
+    }
+
+    pub fn get_estimate(&self) -> Option<Duration> {
+        self.estimate_minutes.map(Duration::minutes)
+    }
+
+    pub fn get_time_entries(&self) -> &[TimeEntry] {
+        &self.time_entries
+    }
+
+    /// Whether a timer is currently running on this todo
+    pub fn has_running_timer(&self) -> bool {
+        self.time_entries.iter().any(|entry| entry.is_running())
+    }
+
+    /// Total tracked time, including a running timer up to `now`
+    pub fn tracked_time(&self, now: DateTime<Utc>) -> Duration {
+        self.time_entries
+            .iter()
+            .map(|entry| entry.duration(now))
+            .fold(Duration::zero(), |total, duration| total + duration)
+    }
+
+    pub fn get_commits(&self) -> &[String] {
+        &self.commits
     }
 
     pub fn set_id(&mut self, id: u32) {
@@ -116,7 +225,58 @@ impl Todo {
         self.due_date = Some(due_date);
     }
 
+    pub fn clear_due_date(&mut self) {
+        self.due_date = None;
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    pub fn add_tag(&mut self, tag: String) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|existing| existing != tag);
+    }
+
+    pub fn set_estimate(&mut self, estimate: Duration) {
+        self.estimate_minutes = Some(estimate.num_minutes());
+    }
+
+    pub fn clear_estimate(&mut self) {
+        self.estimate_minutes = None;
+    }
+
+    pub fn set_remind_at(&mut self, remind_at: DateTime<Utc>) {
+        self.remind_at = Some(remind_at);
+    }
+
+    pub fn clear_remind_at(&mut self) {
+        self.remind_at = None;
+    }
+
+    /// Move the todo to the final workflow state, or back to the initial one
     pub fn set_finished(&mut self, finished: bool) {
+        let workflow = workflow::current();
+        if finished {
+            self.set_state(workflow.done().to_string());
+        } else if self.is_finished() {
+            self.set_state(workflow.initial().to_string());
+        }
+    }
+
+    pub fn set_state(&mut self, state: String) {
+        let finished = workflow::current().is_done(&state);
+        if finished && !self.is_finished() {
+            self.finished_at = Some(Utc::now());
+        } else if !finished {
+            self.finished_at = None;
+        }
+        self.state = state;
         self.finished = finished;
     }
 
@@ -124,11 +284,42 @@ impl Todo {
         self.created_at = creation_date;
     }
 
+    pub fn set_source(&mut self, source: SourceLocation) {
+        self.source = Some(source);
+    }
+
+    pub fn add_time_entry(&mut self, entry: TimeEntry) {
+        self.time_entries.push(entry);
+    }
+
+    /// End the running timer, returning the finished entry
+    pub fn stop_timer(&mut self, now: DateTime<Utc>) -> Option<TimeEntry> {
+        let entry = self.time_entries.iter_mut().find(|entry| entry.is_running())?;
+        entry.end = Some(now);
+        Some(entry.clone())
+    }
+
+    /// Attach a commit hash, ignoring hashes that are already attached
+    pub fn add_commit(&mut self, hash: String) {
+        if !self.commits.contains(&hash) {
+            self.commits.push(hash);
+        }
+    }
+
     /// Compare todos by the given sort order (supports chained criteria)
     pub fn compare(&self, other: &Todo, sort_order: &SortCriteria) -> Ordering {
         self.compare_single_criterion(other, sort_order)
     }
 
+    /// Compare todos by a chain of criteria, later ones break ties of earlier ones
+    pub fn compare_by_order(&self, other: &Todo, sort_order: &[SortCriteria]) -> Ordering {
+        sort_order
+            .iter()
+            .map(|criteria| self.compare(other, criteria))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
     /// Compare todos by a single criterion
     fn compare_single_criterion(&self, other: &Todo, sort_order: &SortCriteria) -> Ordering {
         match sort_order {
@@ -170,6 +361,28 @@ impl Todo {
                     (None, None) => Ordering::Equal,
                 }
             }
+            SortCriteria::Estimate => {
+                // Smallest estimate first, no estimate last
+                match (&self.estimate_minutes, &other.estimate_minutes) {
+                    (Some(self_estimate), Some(other_estimate)) => self_estimate.cmp(other_estimate),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+            SortCriteria::EstimateReverse => {
+                // Largest estimate first, no estimate last
+                match (&self.estimate_minutes, &other.estimate_minutes) {
+                    (Some(self_estimate), Some(other_estimate)) => other_estimate.cmp(self_estimate),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+            SortCriteria::Urgency => {
+                // Most urgent first
+                other.urgency().total_cmp(&self.urgency())
+            }
             SortCriteria::TitleAsc => {
                 // A-Z
                 self.title.to_lowercase().cmp(&other.title.to_lowercase())
@@ -179,20 +392,12 @@ impl Todo {
                 other.title.to_lowercase().cmp(&self.title.to_lowercase())
             }
             SortCriteria::Status => {
-                // Unfinished first
-                match (self.finished, other.finished) {
-                    (false, true) => Ordering::Less,
-                    (true, false) => Ordering::Greater,
-                    _ => Ordering::Equal,
-                }
+                // Workflow order, unfinished first
+                self.workflow_position().cmp(&other.workflow_position())
             }
             SortCriteria::StatusReverse => {
-                // Finished first
-                match (self.finished, other.finished) {
-                    (true, false) => Ordering::Less,
-                    (false, true) => Ordering::Greater,
-                    _ => Ordering::Equal,
-                }
+                // Reverse workflow order, finished first
+                other.workflow_position().cmp(&self.workflow_position())
             }
         }
     }
@@ -203,8 +408,19 @@ pub struct TodoBuilder {
     title: String,
     description: Option<String>,
     finished: Option<bool>,
+    state: Option<String>,
     priority: Option<Priority>,
     due_date: Option<NaiveDate>,
+    source: Option<SourceLocation>,
+    estimate: Option<Duration>,
+    tags: Vec<String>,
+    remind_at: Option<DateTime<Utc>>,
+}
+
+impl Default for TodoBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TodoBuilder {
@@ -214,8 +430,13 @@ impl TodoBuilder {
             title: String::new(),
             description: None,
             finished: None,
+            state: None,
             priority: None,
             due_date: None,
+            source: None,
+            estimate: None,
+            tags: Vec::new(),
+            remind_at: None,
         }
     }
 
@@ -239,6 +460,11 @@ impl TodoBuilder {
         self
     }
 
+    pub fn state(mut self, state: impl Into<Option<String>>) -> Self {
+        self.state = state.into();
+        self
+    }
+
     pub fn priority(mut self, priority: impl Into<Option<Priority>>) -> Self {
         self.priority = priority.into();
         self
@@ -249,15 +475,53 @@ impl TodoBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Todo, String> {
+    pub fn estimate(mut self, estimate: impl Into<Option<Duration>>) -> Self {
+        self.estimate = estimate.into();
+        self
+    }
+
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn remind_at(mut self, remind_at: impl Into<Option<DateTime<Utc>>>) -> Self {
+        self.remind_at = remind_at.into();
+        self
+    }
+
+    pub fn source(mut self, source: impl Into<Option<SourceLocation>>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    pub fn build(self) -> Result<Todo, Error> {
+        let workflow = workflow::current();
+        let state = match (self.state, self.finished.unwrap_or_default()) {
+            (Some(state), _) if !workflow.contains(&state) => {
+                return Err(Error::Parse(format!("Unknown workflow state '{}'", state)));
+            }
+            (Some(state), _) => state,
+            (None, true) => workflow.done().to_string(),
+            (None, false) => workflow.initial().to_string(),
+        };
+        let finished = workflow.is_done(&state);
         Ok(Todo {
             id: self.id,
             title: self.title,
             description: self.description,
-            finished: self.finished.unwrap_or_default(),
+            finished,
+            state,
             priority: self.priority.unwrap_or_default(),
             created_at: Utc::now(),
             due_date: self.due_date,
+            source: self.source,
+            commits: Vec::new(),
+            finished_at: finished.then(Utc::now),
+            time_entries: Vec::new(),
+            estimate_minutes: self.estimate.map(|estimate| estimate.num_minutes()),
+            tags: self.tags,
+            remind_at: self.remind_at,
         })
     }
 }
@@ -1,57 +1,110 @@
+use crate::error::Error;
 use crate::sort_order::SortCriteria;
 use crate::sync::GitRepo;
+use crate::config::Config;
 use crate::{config, todo::Todo};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TodoList {
     pub todos: Vec<Todo>,
+    /// Name of the list the todos are stored in
+    #[serde(skip)]
+    name: String,
+    /// File the list is stored in
+    #[serde(skip)]
+    path: PathBuf,
+    /// Whether the list lives in a project-local `.utodo` directory
+    #[serde(skip)]
+    local: bool,
 }
 
 impl TodoList {
-    pub fn new() -> Self {
-        TodoList { todos: Vec::new() }
+    /// An empty list stored in the storage directory of `config`
+    pub fn empty(config: &Config, name: &str) -> Self {
+        TodoList {
+            todos: Vec::new(),
+            name: name.to_string(),
+            path: config.get_list_file_path(name),
+            local: config.is_local(),
+        }
+    }
+
+    /// Load the list selected by `--list` or the configured default list
+    pub fn load() -> Result<Self, Error> {
+        Self::load_list(&config::active_list_name()?)
+    }
+
+    pub fn load_list(name: &str) -> Result<Self, Error> {
+        Self::load_from(&config::load_config()?, name)
+    }
+
+    /// Load a list from the storage directory of `config`
+    pub fn load_from(config: &Config, name: &str) -> Result<Self, Error> {
+        config::validate_list_name(name)?;
+        let list = Self::empty(config, name);
+        if !list.path.exists() && name != config::DEFAULT_LIST_NAME {
+            return Err(Error::NotFound(format!("List '{}' does not exist", name)));
+        }
+        list.read()
+    }
+
+    /// Load the archive of the list selected by `--list` or the configured default list
+    pub fn load_archive() -> Result<Self, Error> {
+        let config = config::load_config()?;
+        Self::load_archive_from(&config, &config::active_list_for(&config))
+    }
+
+    /// Load the archived todos of a list, empty if nothing was archived yet
+    pub fn load_archive_from(config: &Config, name: &str) -> Result<Self, Error> {
+        config::validate_list_name(name)?;
+        TodoList {
+            path: config.get_archive_file_path(name),
+            ..Self::empty(config, name)
+        }
+        .read()
     }
 
-    pub fn load() -> io::Result<Self> {
-        let path = config::get_data_path();
-        if !path.exists() {
-            return Ok(TodoList::new());
+    /// Read the todos from the file of the list, a missing file means no todos
+    fn read(mut self) -> Result<Self, Error> {
+        if !self.path.exists() {
+            return Ok(self);
         }
-        let file = File::open(path)?;
+        let file = File::open(&self.path)?;
         let reader = BufReader::new(file);
-        let mut list = Self::new();
 
         for (i, line) in reader.lines().enumerate() {
             let line = line?;
             if !line.trim().is_empty() {
-                let todo = Todo::from_json_line(&line, i as u32)?;
-                list.add(todo);
+                let todo = Todo::from_json_line(&line, i as u32)
+                    .map_err(|e| Error::corrupt(&self.path, i + 1, e))?;
+                self.add(todo);
             }
         }
 
-        Ok(list)
+        Ok(self)
     }
 
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let path = config::get_data_path();
-        File::create(path)?;
-        self.todos.iter().try_for_each(|todo| todo.save_to_file())?;
-
-        let data_dir = config::get_data_dir()?;
-        let repo = GitRepo::new(data_dir);
+    pub fn save(&self) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        File::create(&self.path)?;
+        self.todos.iter().try_for_each(|todo| todo.save_to_file(&self.path))?;
 
-        let auto_sync = config::get_auto_sync_enabled()?;
-        if  auto_sync {
-            if let Err(e) = repo.sync_file(config::TODO_FILE_NAME) {
-                eprintln!("Git sync failed: {}", e);
-            }
+        // Project-local todos are versioned with the project, not synced
+        if self.local {
+            return Ok(());
         }
+        sync_if_enabled()
+    }
 
-        Ok(())
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 
     pub fn add(&mut self, task: Todo) {
@@ -74,17 +127,26 @@ impl TodoList {
         self.todos.get_mut(id)
     }
 
-    /// Sort todos by the given sort order
-    pub fn sort_by_order(&mut self, sort_order: &Vec<SortCriteria>) {
-        self.todos.sort_by(|a, b| {
-            for criteria in sort_order {
-                let ordering = a.compare(b, criteria);
-                if ordering != Ordering::Equal {
-                    return ordering;
-                }
-            }
-            Ordering::Equal
+    /// Todos in the given sort order, the stored order (and thus the IDs) stays untouched
+    pub fn sorted(&self, sort_order: &[SortCriteria]) -> Vec<Todo> {
+        let mut todos = self.todos.clone();
+        todos.sort_by(|a, b| a.compare_by_order(b, sort_order));
+        todos
+    }
+
+    /// Move finished todos into `archive`, only those finished before `finished_before`
+    /// if given. Returns the number of archived todos.
+    pub fn archive_finished(&mut self, archive: &mut TodoList, finished_before: Option<DateTime<Utc>>) -> usize {
+        let (archived, kept): (Vec<Todo>, Vec<Todo>) = self.todos.drain(..).partition(|todo| {
+            todo.is_finished()
+                && finished_before.is_none_or(|before| {
+                    todo.get_finished_at().is_none_or(|finished_at| finished_at < before)
+                })
         });
+        self.todos = kept;
+        let count = archived.len();
+        archive.todos.extend(archived);
+        count
     }
 
     pub fn todos_as_vec(&self) -> Vec<Todo> {
@@ -92,6 +154,21 @@ impl TodoList {
     }
 }
 
+/// Sync all lists with the git remote if auto-sync is enabled
+pub fn sync_if_enabled() -> Result<(), Error> {
+    let config = config::load_config()?;
+    if !config.auto_sync_enabled || config.is_local() {
+        return Ok(());
+    }
+
+    let data_dir = config::get_data_dir()?;
+    let repo = GitRepo::new(data_dir);
+    if let Err(e) = repo.sync_todos_queued() {
+        eprintln!("Git sync failed, change will be synced later: {}", e);
+    }
+    Ok(())
+}
+
 pub fn todos_from_json_lines(lines: &[String]) -> Vec<Todo> {
     lines
         .iter()
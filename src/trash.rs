@@ -0,0 +1,124 @@
+use crate::config::{self, Config};
+use crate::error::Error;
+use crate::todo::Todo;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A removed todo together with the list it was removed from
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashedTodo {
+    pub deleted_at: DateTime<Utc>,
+    pub list: String,
+    pub todo: Todo,
+}
+
+/// Removed todos kept for restoring, stored next to the lists they came from
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Trash {
+    entries: Vec<TrashedTodo>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Trash {
+    /// Load the trash of the storage directory of `config`, purging entries older
+    /// than the configured retention period
+    pub fn load(config: &Config) -> Result<Self, Error> {
+        let path = config.storage_path().join(config::TRASH_FILE_NAME);
+        let mut trash = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| Error::corrupt(&path, e.line(), e))?
+        } else {
+            Trash::default()
+        };
+        trash.path = path;
+
+        let retention_days = config.get_trash_retention_days();
+        if retention_days > 0 {
+            let cutoff = Utc::now() - Duration::days(retention_days as i64);
+            let before = trash.entries.len();
+            trash.entries.retain(|entry| entry.deleted_at >= cutoff);
+            if trash.entries.len() != before {
+                trash.save()?;
+            }
+        }
+        Ok(trash)
+    }
+
+    /// Save the trash, removing the file once it is empty
+    pub fn save(&self) -> Result<(), Error> {
+        if self.entries.is_empty() {
+            if self.path.exists() {
+                fs::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self).map_err(io::Error::from)?)?;
+        Ok(())
+    }
+
+    /// Merge two versions of the trash file, e.g. both sides of a sync conflict, keeping the
+    /// entries of both. A missing or empty version counts as an empty trash.
+    pub fn merge_files(ours: &str, theirs: &str) -> Result<String, serde_json::Error> {
+        let parse = |content: &str| -> Result<Vec<TrashedTodo>, serde_json::Error> {
+            if content.trim().is_empty() {
+                return Ok(Vec::new());
+            }
+            Ok(serde_json::from_str::<Trash>(content)?.entries)
+        };
+        let mut entries = parse(ours)?;
+        for entry in parse(theirs)? {
+            let known = entries.iter().any(|existing| {
+                existing.deleted_at == entry.deleted_at
+                    && existing.list == entry.list
+                    && existing.todo.get_creation_date() == entry.todo.get_creation_date()
+            });
+            if !known {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|entry| entry.deleted_at);
+        serde_json::to_string_pretty(&Trash {
+            entries,
+            path: PathBuf::new(),
+        })
+    }
+
+    pub fn add(&mut self, list: &str, todo: Todo) {
+        self.entries.push(TrashedTodo {
+            deleted_at: Utc::now(),
+            list: list.to_string(),
+            todo,
+        });
+    }
+
+    pub fn entries(&self) -> &[TrashedTodo] {
+        &self.entries
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<TrashedTodo> {
+        if index < self.entries.len() {
+            Some(self.entries.remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
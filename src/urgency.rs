@@ -0,0 +1,94 @@
+use crate::config;
+use crate::error::Error;
+use crate::todo::Todo;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Days before the due date from which due-date proximity starts to count fully
+const DUE_WINDOW_DAYS: f64 = 14.0;
+/// Age in days at which the age term reaches its full weight
+const MAX_AGE_DAYS: f64 = 365.0;
+
+/// Weights of the terms making up the urgency score, configurable in `Config`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct UrgencyCoefficients {
+    /// Weight of High priority, Medium counts half, Low nothing
+    pub priority: f64,
+    /// Weight of an approaching due date, from 0.2 two weeks out to 1.0 when due
+    pub due: f64,
+    /// Added on top once the due date has passed
+    pub overdue: f64,
+    /// Weight of the age since creation, full after a year
+    pub age: f64,
+    /// Weight per tag, e.g. {"bug": 3.0}
+    pub tags: HashMap<String, f64>,
+}
+
+impl Default for UrgencyCoefficients {
+    fn default() -> Self {
+        UrgencyCoefficients {
+            priority: 6.0,
+            due: 12.0,
+            overdue: 4.0,
+            age: 2.0,
+            tags: HashMap::new(),
+        }
+    }
+}
+
+impl UrgencyCoefficients {
+    /// Urgency of a todo, finished todos are never urgent
+    pub fn score(&self, todo: &Todo, today: NaiveDate) -> f64 {
+        if todo.is_finished() {
+            return 0.0;
+        }
+
+        let priority = (todo.get_priority().priority_value() - 1) as f64 / 2.0;
+
+        let due = match todo.get_due_date() {
+            Some(due) => {
+                let days_left = (due - today).num_days() as f64;
+                if days_left <= 0.0 {
+                    1.0
+                } else {
+                    (1.0 - days_left / DUE_WINDOW_DAYS * 0.8).max(0.2)
+                }
+            }
+            None => 0.0,
+        };
+        let overdue = if todo.is_overdue() { 1.0 } else { 0.0 };
+
+        let age_days = (today - todo.get_creation_date().date_naive()).num_days() as f64;
+        let age = (age_days / MAX_AGE_DAYS).clamp(0.0, 1.0);
+
+        let tags: f64 = todo
+            .get_tags()
+            .iter()
+            .filter_map(|tag| self.tags.get(tag))
+            .sum();
+
+        self.priority * priority + self.due * due + self.overdue * overdue + self.age * age + tags
+    }
+}
+
+static COEFFICIENTS: OnceLock<UrgencyCoefficients> = OnceLock::new();
+
+/// Use `coefficients` for this process instead of reading them from the config. Library users
+/// call this before computing urgencies, it fails once the coefficients are in use.
+pub fn set_coefficients(coefficients: UrgencyCoefficients) -> Result<(), Error> {
+    COEFFICIENTS
+        .set(coefficients)
+        .map_err(|_| Error::Config("The urgency coefficients are already in use".to_string()))
+}
+
+/// Coefficients set with `set_coefficients`, else those from the config loaded once per invocation
+pub fn coefficients() -> &'static UrgencyCoefficients {
+    COEFFICIENTS.get_or_init(|| {
+        config::load_config()
+            .map(|config| config.get_urgency().clone())
+            .unwrap_or_default()
+    })
+}
@@ -0,0 +1,228 @@
+use crate::error::Error;
+use crate::priority::Priority;
+use crate::selection::{Filter, parse_filter};
+use crate::sort_order::SortCriteria;
+use crate::todo::Todo;
+use crate::workflow;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// A column of the todo table
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum)]
+pub enum Column {
+    Id,
+    Title,
+    Description,
+    Finished,
+    Priority,
+    Urgency,
+    Estimate,
+    CreatedAt,
+    DueDate,
+    Tags,
+}
+
+impl Column {
+    /// Columns of the table shown by `show`
+    pub const DEFAULT: [Column; 9] = [
+        Column::Id,
+        Column::Title,
+        Column::Description,
+        Column::Finished,
+        Column::Priority,
+        Column::Urgency,
+        Column::Estimate,
+        Column::CreatedAt,
+        Column::DueDate,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Title => "title",
+            Column::Description => "description",
+            Column::Finished => "finished",
+            Column::Priority => "priority",
+            Column::Urgency => "urgency",
+            Column::Estimate => "estimate",
+            Column::CreatedAt => "created_at",
+            Column::DueDate => "due_date",
+            Column::Tags => "tags",
+        }
+    }
+}
+
+/// What the todos of a view are grouped into, one table per group
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum)]
+pub enum GroupBy {
+    Priority,
+    State,
+    DueDate,
+    Tag,
+}
+
+/// A saved combination of filter, sort order, columns and grouping, run with `view <name>`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct View {
+    /// Filter expression as given to `--where`, all todos if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Sort order, the configured one if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<SortCriteria>,
+    /// Columns to show, those of `show` if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<Column>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<GroupBy>,
+}
+
+/// Views available without configuring them, saved views with the same name take precedence
+pub fn builtin_views() -> Vec<(&'static str, View)> {
+    vec![
+        (
+            "next",
+            View {
+                filter: Some("open".to_string()),
+                sort: vec![SortCriteria::Urgency],
+                columns: vec![Column::Id, Column::Title, Column::Priority, Column::Urgency, Column::DueDate],
+                group_by: None,
+            },
+        ),
+        (
+            "overdue",
+            View {
+                filter: Some("overdue".to_string()),
+                sort: vec![SortCriteria::DueDate],
+                columns: vec![Column::Id, Column::Title, Column::Priority, Column::DueDate],
+                group_by: Some(GroupBy::Priority),
+            },
+        ),
+        (
+            "done-this-week",
+            View {
+                filter: Some("finished:this-week".to_string()),
+                sort: vec![SortCriteria::CreatedAsc],
+                columns: vec![Column::Id, Column::Title, Column::Estimate, Column::Tags],
+                group_by: None,
+            },
+        ),
+    ]
+}
+
+impl View {
+    pub fn parsed_filter(&self) -> Result<Option<Filter>, Error> {
+        self.filter.as_deref().map(parse_filter).transpose()
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        if self.columns.is_empty() {
+            &Column::DEFAULT
+        } else {
+            &self.columns
+        }
+    }
+
+    /// One line summary for `view list`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(filter) = &self.filter {
+            parts.push(format!("where {}", filter));
+        }
+        if !self.sort.is_empty() {
+            let sort: Vec<String> = self.sort.iter().map(value_name).collect();
+            parts.push(format!("sorted by {}", sort.join(",")));
+        }
+        if !self.columns.is_empty() {
+            let columns: Vec<String> = self.columns.iter().map(value_name).collect();
+            parts.push(format!("columns {}", columns.join(",")));
+        }
+        if let Some(group_by) = &self.group_by {
+            parts.push(format!("grouped by {}", value_name(group_by)));
+        }
+        if parts.is_empty() {
+            "all todos".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Split already sorted todos into titled groups, keeping their order within a group.
+    /// Without grouping or todos everything ends up in a single group titled `title`.
+    pub fn group(&self, title: &str, todos: Vec<Todo>) -> Vec<(String, Vec<Todo>)> {
+        let Some(group_by) = self.group_by else {
+            return vec![(title.to_string(), todos)];
+        };
+        let keys: Vec<String> = match group_by {
+            GroupBy::Priority => [Priority::High, Priority::Medium, Priority::Low]
+                .iter()
+                .map(|priority| priority.to_string())
+                .collect(),
+            GroupBy::State => workflow::current().states().to_vec(),
+            GroupBy::DueDate => {
+                let mut dates: Vec<_> = todos.iter().filter_map(Todo::get_due_date).collect();
+                dates.sort();
+                dates.dedup();
+                dates.iter().map(|date| date.format("%d.%m.%Y").to_string()).collect()
+            }
+            GroupBy::Tag => {
+                let mut tags: Vec<String> = todos.iter().flat_map(|todo| todo.get_tags().to_vec()).collect();
+                tags.sort();
+                tags.dedup();
+                tags
+            }
+        };
+        // Every todo has a priority and a workflow position, only dates and tags can be missing
+        let fallback = match group_by {
+            GroupBy::DueDate => Some("No due date"),
+            GroupBy::Tag => Some("No tag"),
+            GroupBy::Priority | GroupBy::State => None,
+        };
+
+        let mut groups: Vec<(String, Vec<Todo>)> = keys.into_iter().map(|key| (key, Vec::new())).collect();
+        let mut rest = Vec::new();
+        for todo in todos {
+            let todo_keys = group_keys(group_by, &todo);
+            let mut placed = false;
+            for (key, members) in groups.iter_mut() {
+                if todo_keys.contains(key) {
+                    members.push(todo.clone());
+                    placed = true;
+                }
+            }
+            if !placed {
+                rest.push(todo);
+            }
+        }
+        if let Some(fallback) = fallback {
+            groups.push((fallback.to_string(), rest));
+        }
+        groups.retain(|(_, members)| !members.is_empty());
+        if groups.is_empty() {
+            groups.push((title.to_string(), Vec::new()));
+        }
+        groups
+    }
+}
+
+/// Keys of the groups a todo belongs to, several for todos with several tags
+fn group_keys(group_by: GroupBy, todo: &Todo) -> Vec<String> {
+    match group_by {
+        GroupBy::Priority => vec![todo.get_priority().to_string()],
+        GroupBy::State => vec![workflow::current().states()[todo.workflow_position()].clone()],
+        GroupBy::DueDate => todo
+            .get_due_date()
+            .map(|date| date.format("%d.%m.%Y").to_string())
+            .into_iter()
+            .collect(),
+        GroupBy::Tag => todo.get_tags().to_vec(),
+    }
+}
+
+/// Name of a value as written on the command line
+fn value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
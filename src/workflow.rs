@@ -0,0 +1,87 @@
+use crate::config;
+use crate::error::Error;
+use std::sync::OnceLock;
+
+/// Default workflow for configs that do not define one
+pub const DEFAULT_STATES: [&str; 3] = ["todo", "doing", "done"];
+
+/// Ordered states a todo moves through. The first state is the initial one,
+/// the last state means the todo is finished.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workflow {
+    states: Vec<String>,
+}
+
+impl Workflow {
+    pub fn new(states: Vec<String>) -> Result<Self, Error> {
+        if states.len() < 2 {
+            return Err(Error::Parse("A workflow needs at least two states".to_string()));
+        }
+        for (i, state) in states.iter().enumerate() {
+            if state.trim().is_empty() {
+                return Err(Error::Parse("Workflow states must not be empty".to_string()));
+            }
+            if states[..i].contains(state) {
+                return Err(Error::Parse(format!("Workflow state '{}' is listed twice", state)));
+            }
+        }
+        Ok(Workflow { states })
+    }
+
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    pub fn initial(&self) -> &str {
+        &self.states[0]
+    }
+
+    pub fn done(&self) -> &str {
+        &self.states[self.states.len() - 1]
+    }
+
+    pub fn contains(&self, state: &str) -> bool {
+        self.states.iter().any(|existing| existing == state)
+    }
+
+    pub fn is_done(&self, state: &str) -> bool {
+        state == self.done()
+    }
+
+    /// Position of a state in the workflow, unknown states count as initial
+    pub fn position(&self, state: &str) -> usize {
+        self.states
+            .iter()
+            .position(|existing| existing == state)
+            .unwrap_or(0)
+    }
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Workflow {
+            states: DEFAULT_STATES.iter().map(|state| state.to_string()).collect(),
+        }
+    }
+}
+
+static WORKFLOW: OnceLock<Workflow> = OnceLock::new();
+
+/// Use `workflow` for this process instead of reading it from the config. Library users call
+/// this before creating or loading todos, it fails once the workflow is in use.
+pub fn set_current(workflow: Workflow) -> Result<(), Error> {
+    WORKFLOW
+        .set(workflow)
+        .map_err(|_| Error::Config("The workflow is already in use".to_string()))
+}
+
+/// Workflow set with `set_current`, else the one from the config loaded once per invocation.
+/// `Config::load` rejects invalid workflows, so commands have reported a broken config
+/// before they get here.
+pub fn current() -> &'static Workflow {
+    WORKFLOW.get_or_init(|| match config::load_config() {
+        Ok(config) => Workflow::new(config.get_workflow().to_vec())
+            .expect("Config::load validates the workflow"),
+        Err(_) => Workflow::default(),
+    })
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use common::{TestEnv, stderr, stdout};
+
+fn run(env: &TestEnv, args: &[&str]) -> String {
+    let output = env.utodo(args);
+    assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    stdout(&output)
+}
+
+fn date(days_from_today: i64) -> String {
+    (Utc::now().date_naive() + Duration::days(days_from_today))
+        .format("%d.%m.%Y")
+        .to_string()
+}
+
+/// Section label above the line mentioning `title`
+fn section_of(agenda: &str, title: &str) -> Option<String> {
+    let mut section = None;
+    for line in agenda.lines() {
+        if !line.starts_with(' ') && !line.is_empty() {
+            section = Some(line.to_string());
+        } else if line.contains(title) {
+            return section;
+        }
+    }
+    None
+}
+
+fn add_dated_todos(env: &TestEnv) {
+    run(env, &["add", "--title", "late", "--due-date", &date(-3)]);
+    run(env, &["add", "--title", "now", "--due-date", &date(0)]);
+    run(env, &["add", "--title", "next day", "--due-date", &date(1)]);
+    run(env, &["add", "--title", "in two days", "--due-date", &date(2)]);
+    run(env, &["add", "--title", "in ten days", "--due-date", &date(10)]);
+    run(env, &["add", "--title", "far away", "--due-date", &date(30)]);
+    run(env, &["add", "--title", "whenever"]);
+    run(env, &["add", "--title", "already done", "--due-date", &date(0)]);
+    run(env, &["complete", "7"]);
+}
+
+#[test]
+fn todos_are_bucketed_by_due_date() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    add_dated_todos(&env);
+
+    let agenda = run(&env, &["agenda"]);
+    assert_eq!(section_of(&agenda, "late").as_deref(), Some("Overdue"), "{}", agenda);
+    assert_eq!(section_of(&agenda, "now").as_deref(), Some("Today"), "{}", agenda);
+    assert_eq!(section_of(&agenda, "next day").as_deref(), Some("Tomorrow"), "{}", agenda);
+    // The week ends on Sunday, from Saturday on two days ahead is next week
+    let today: NaiveDate = Utc::now().date_naive();
+    let in_two_days = if today.weekday().num_days_from_monday() <= 4 { "This week" } else { "Later" };
+    assert_eq!(section_of(&agenda, "in two days").as_deref(), Some(in_two_days), "{}", agenda);
+    assert_eq!(section_of(&agenda, "in ten days").as_deref(), Some("Later"), "{}", agenda);
+    assert_eq!(section_of(&agenda, "whenever").as_deref(), Some("No date"), "{}", agenda);
+    assert!(!agenda.contains("already done"), "{}", agenda);
+    assert!(!agenda.contains("far away"), "{}", agenda);
+
+    let sections: Vec<&str> = agenda
+        .lines()
+        .filter(|line| !line.starts_with(' ') && !line.is_empty())
+        .collect();
+    assert_eq!(sections.first(), Some(&"Overdue"));
+    assert_eq!(sections.last(), Some(&"No date"));
+
+    let dated = run(&env, &["agenda", "--no-undated"]);
+    assert!(!dated.contains("whenever") && !dated.contains("No date"), "{}", dated);
+}
+
+#[test]
+fn horizon_limits_how_far_ahead_the_agenda_looks() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    add_dated_todos(&env);
+
+    let wide = run(&env, &["agenda", "--horizon", "40"]);
+    assert_eq!(section_of(&wide, "far away").as_deref(), Some("Later"), "{}", wide);
+
+    let narrow = run(&env, &["agenda", "--horizon", "1"]);
+    assert!(narrow.contains("next day") && narrow.contains("late"), "{}", narrow);
+    assert!(!narrow.contains("in two days") && !narrow.contains("in ten days"), "{}", narrow);
+    assert!(narrow.contains("whenever"), "{}", narrow);
+}
+
+#[test]
+fn compact_mode_prints_one_line_per_todo() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    add_dated_todos(&env);
+
+    let compact = run(&env, &["agenda", "--compact"]);
+    let lines: Vec<&str> = compact.lines().collect();
+    assert_eq!(lines.len(), 6, "{}", compact);
+    assert_eq!(lines[0], format!("Overdue: #0 late ({})", date(-3)));
+    assert!(lines.contains(&format!("Today: #1 now ({})", date(0)).as_str()), "{}", compact);
+    assert_eq!(lines[5], "No date: #6 whenever");
+}
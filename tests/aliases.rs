@@ -0,0 +1,95 @@
+mod common;
+
+use chrono::Utc;
+use common::{TestEnv, stderr, stdout};
+use serde_json::{Value, json};
+use std::fs;
+
+fn set_aliases(env: &TestEnv, aliases: Value) {
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["aliases"] = aliases;
+    fs::write(env.config_file(), config.to_string()).unwrap();
+}
+
+fn run(env: &TestEnv, args: &[&str]) -> String {
+    let output = env.utodo(args);
+    assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    stdout(&output)
+}
+
+#[test]
+fn short_forms_from_the_readme_work() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    run(&env, &["a", "--title", "buy groceries"]);
+    run(&env, &["a", "--title", "finish project documentation", "--description", "chapter on sync"]);
+    run(&env, &["a", "--title", "water plants"]);
+    assert!(run(&env, &["ls"]).contains("buy groceries"));
+
+    run(&env, &["done", "0"]);
+    run(&env, &["c", "2"]);
+    let found = run(&env, &["search", "PROJECT"]);
+    assert!(found.contains("finish project"), "{}", found);
+    assert!(!found.contains("buy groceries"), "{}", found);
+    assert!(run(&env, &["find", "sync"]).contains("finish project"));
+
+    run(&env, &["clear-completed", "--yes"]);
+    let remaining = run(&env, &["l"]);
+    assert!(remaining.contains("finish project"), "{}", remaining);
+    assert!(!remaining.contains("buy groceries") && !remaining.contains("water plants"), "{}", remaining);
+
+    run(&env, &["rm", "0"]);
+    assert!(run(&env, &["trash", "list"]).contains("finish project"));
+    assert!(run(&env, &["h"]).contains("Usage"));
+}
+
+#[test]
+fn configured_aliases_expand_to_command_lines() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    set_aliases(
+        &env,
+        json!({
+            "today": "show --where \"due:today and open\"",
+            "urgent": "add --priority high --title",
+            "show": "remove 0",
+        }),
+    );
+    let today = Utc::now().date_naive().format("%d.%m.%Y").to_string();
+    run(&env, &["add", "--title", "due now", "--due-date", &today]);
+    run(&env, &["add", "--title", "some day"]);
+    run(&env, &["urgent", "fix production"]);
+
+    let due = run(&env, &["--global", "today"]);
+    assert!(due.contains("due now"), "{}", due);
+    assert!(!due.contains("some day") && !due.contains("fix production"), "{}", due);
+
+    let todos: Vec<Value> = fs::read_to_string(env.data_dir().join("todos.json"))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(todos[2]["title"], "fix production");
+    assert_eq!(todos[2]["priority"], "High");
+
+    // Commands win over aliases with the same name
+    assert!(run(&env, &["show"]).contains("due now"));
+}
+
+#[test]
+fn broken_aliases_are_config_errors() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    set_aliases(&env, json!({ "ping": "pong", "pong": "ping", "quote": "show --where \"open" }));
+
+    let output = env.utodo(&["ping"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(stderr(&output).contains("Alias 'ping' expands to itself"), "{}", stderr(&output));
+
+    let output = env.utodo(&["quote"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(stderr(&output).contains("Alias 'quote' is not a valid command line"), "{}", stderr(&output));
+
+    // Unknown commands are still reported by the argument parser
+    assert_eq!(env.utodo(&["nope"]).status.code(), Some(2));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn archive_moves_finished_todos_and_unarchive_restores_them() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let run = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    for title in ["open one", "done one", "done two"] {
+        run(&["add", "--title", title]);
+    }
+    run(&["complete", "1-2", "--yes"]);
+
+    // Just finished, so not old enough yet
+    assert!(run(&["archive", "--older-than", "7"]).contains("Archived 0 todos"));
+    assert!(run(&["archive"]).contains("Archived 2 todos"));
+
+    let shown = run(&["show"]);
+    assert!(shown.contains("open one") && !shown.contains("done one"), "{}", shown);
+    let archived = run(&["show", "--archived"]);
+    assert!(archived.contains("done one") && archived.contains("done two"), "{}", archived);
+    assert!(!archived.contains("open one"), "{}", archived);
+    assert!(env.data_dir().join("archive").join("todos.json").exists());
+
+    let stats = run(&["stats", "--include-archive", "--json"]);
+    assert!(stats.contains("\"finished\": 2"), "{}", stats);
+    let stats = run(&["stats", "--json"]);
+    assert!(stats.contains("\"finished\": 0"), "{}", stats);
+
+    assert!(run(&["unarchive", "0"]).contains("Restored 'done one' as todo with ID 1"));
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(stored.contains("done one"), "{}", stored);
+    let archive = fs::read_to_string(env.data_dir().join("archive").join("todos.json")).unwrap();
+    assert!(!archive.contains("done one") && archive.contains("done two"), "{}", archive);
+}
+
+#[test]
+fn write_commands_auto_archive_when_configured() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "finished task"]).status.success());
+    assert!(env.utodo(&["complete", "0"]).status.success());
+
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["auto_archive_days"] = serde_json::json!(0);
+    fs::write(env.config_file(), config.to_string()).unwrap();
+
+    // Reading never archives
+    let shown = stdout(&env.utodo(&["show"]));
+    assert!(shown.contains("finished task"), "{}", shown);
+    assert!(!env.data_dir().join("archive").exists());
+
+    assert!(env.utodo(&["add", "--title", "next task"]).status.success());
+    let shown = stdout(&env.utodo(&["show"]));
+    assert!(!shown.contains("finished task") && shown.contains("next task"), "{}", shown);
+    let archived = stdout(&env.utodo(&["show", "--archived"]));
+    assert!(archived.contains("finished task"), "{}", archived);
+}
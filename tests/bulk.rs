@@ -0,0 +1,81 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+use std::io::Write;
+use std::process::Stdio;
+
+#[test]
+fn complete_and_remove_accept_ranges_and_filters() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let run = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    for (title, priority) in [("a", "low"), ("b", "high"), ("c", "low"), ("d", "low"), ("e", "medium")] {
+        run(&["add", "--title", title, "-p", priority]);
+    }
+
+    let output = run(&["complete", "0,2-3", "--yes"]);
+    assert_eq!(output.lines().count(), 3, "{}", output);
+
+    // Without --yes the preview is shown and a declined prompt changes nothing
+    let mut child = env
+        .command()
+        .args(["remove", "--where", "priority:low and finished"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"n\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let preview = stdout(&output);
+    assert!(preview.contains("Remove 3 todos") && preview.contains("#3 d"), "{}", preview);
+    assert!(preview.contains("Aborted"), "{}", preview);
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert_eq!(stored.lines().count(), 5);
+
+    run(&["remove", "--where", "priority:low and finished", "--yes"]);
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert_eq!(stored.lines().count(), 2);
+    assert!(stored.contains("\"b\"") && stored.contains("\"e\""), "{}", stored);
+
+    let output = env.utodo(&["complete", "7"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Todo with ID 7 not found"));
+}
+
+#[test]
+fn update_applies_changes_to_every_selected_todo() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for title in ["a", "b", "c"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+
+    let output = env.utodo(&["update", "--id", "1-2", "-p", "high", "--yes"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = stdout(&env.utodo(&["complete", "--where", "priority:high", "--yes"]));
+    assert!(output.contains("ID 1 completed") && output.contains("ID 2 completed"), "{}", output);
+    assert!(!output.contains("ID 0"), "{}", output);
+}
+
+#[test]
+fn ranges_beyond_the_list_are_rejected_without_expanding_them() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for title in ["a", "b"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+
+    let output = env.utodo(&["complete", "0-18446744073709551615", "--yes"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("Todo with ID 2 not found"), "{}", stderr(&output));
+    let output = env.utodo(&["remove", "1,5-7", "--yes"]);
+    assert!(stderr(&output).contains("Todo with ID 5 not found"), "{}", stderr(&output));
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(!stored.contains("\"finished\":true") && stored.lines().count() == 2, "{}", stored);
+}
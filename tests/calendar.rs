@@ -0,0 +1,105 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+
+const RED: &str = "\u{1b}[31m";
+const YELLOW: &str = "\u{1b}[33m";
+const GREEN: &str = "\u{1b}[32m";
+const RESET: &str = "\u{1b}[39m";
+
+fn run(env: &TestEnv, args: &[&str]) -> String {
+    let output = env.utodo(args);
+    assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    stdout(&output)
+}
+
+/// Cells of the calendar grid without borders, one vector per printed line
+fn grid(calendar: &str) -> Vec<Vec<String>> {
+    calendar
+        .lines()
+        .filter(|line| line.starts_with('│'))
+        .map(|line| {
+            line.trim_matches('│')
+                .split('│')
+                .map(|cell| cell.trim().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// The cell of `day` and the cell below it, which lists the todos due that day
+fn day_cells(calendar: &str, day: u32) -> (String, String) {
+    let grid = grid(calendar);
+    let plain = |cell: &str| {
+        [RED, YELLOW, GREEN, RESET]
+            .iter()
+            .fold(cell.to_string(), |cell, color| cell.replace(color, ""))
+    };
+    for (row, cells) in grid.iter().enumerate() {
+        if let Some(column) = cells.iter().position(|cell| plain(cell) == day.to_string()) {
+            let below = grid.get(row + 1).map(|cells| cells[column].clone()).unwrap_or_default();
+            return (cells[column].clone(), below);
+        }
+    }
+    panic!("day {} not in calendar:\n{}", day, calendar);
+}
+
+#[test]
+fn month_argument_is_parsed_and_validated() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    let march = run(&env, &["calendar", "03.2024"]);
+    assert!(march.contains("March 2024"), "{}", march);
+    // March 2024 starts on a Friday
+    let first_row = &grid(&march)[1];
+    assert_eq!(first_row[4], "1", "{}", march);
+    assert!(first_row[..4].iter().all(String::is_empty), "{}", march);
+
+    assert!(run(&env, &["calendar", "2.2099"]).contains("February 2099"));
+    for invalid in ["13.2024", "0.2024", "march", "03-2024"] {
+        let output = env.utodo(&["calendar", invalid]);
+        assert_eq!(output.status.code(), Some(2), "{}", invalid);
+        assert!(stderr(&output).contains("Invalid month format"), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn due_todos_are_counted_on_their_day() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    run(&env, &["add", "--title", "first", "--due-date", "15.03.2099"]);
+    run(&env, &["add", "--title", "second", "--due-date", "15.03.2099", "--priority", "medium"]);
+    run(&env, &["add", "--title", "third", "--due-date", "20.03.2099"]);
+    run(&env, &["add", "--title", "finished", "--due-date", "20.03.2099"]);
+    run(&env, &["add", "--title", "other month", "--due-date", "15.04.2099"]);
+    run(&env, &["complete", "3"]);
+
+    let calendar = run(&env, &["calendar", "03.2099"]);
+    assert!(day_cells(&calendar, 15).1.contains("2 todos"), "{}", calendar);
+    assert!(day_cells(&calendar, 20).1.contains("1 todo"), "{}", calendar);
+    assert!(!day_cells(&calendar, 20).1.contains("todos"), "{}", calendar);
+    assert!(day_cells(&calendar, 16).1.is_empty(), "{}", calendar);
+
+    let titles = run(&env, &["calendar", "03.2099", "--titles"]);
+    assert!(day_cells(&titles, 20).1.contains("third"), "{}", titles);
+    assert!(!titles.contains("finished") && !titles.contains("other month"), "{}", titles);
+}
+
+#[test]
+fn overdue_days_are_highlighted() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    run(&env, &["add", "--title", "missed", "--due-date", "15.03.2024"]);
+    run(&env, &["add", "--title", "planned", "--due-date", "15.03.2099", "--priority", "medium"]);
+
+    let past = run(&env, &["calendar", "03.2024"]);
+    let (day, todos) = day_cells(&past, 15);
+    assert!(day.starts_with(RED) && todos.starts_with(RED), "{}", past);
+    assert!(!day_cells(&past, 14).0.contains(RED), "{}", past);
+
+    // Upcoming days take the color of their highest priority instead
+    let future = run(&env, &["calendar", "03.2099"]);
+    let (day, _) = day_cells(&future, 15);
+    assert!(day.starts_with(YELLOW) && !future.contains(RED), "{}", future);
+}
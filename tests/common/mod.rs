@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Isolated home, config and data directories for running the binary
+pub struct TestEnv {
+    root: TempDir,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let root = tempfile::tempdir().expect("failed to create temp dir");
+        fs::create_dir_all(root.path().join("config")).unwrap();
+        fs::create_dir_all(root.path().join("data")).unwrap();
+        TestEnv { root }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.root.path()
+    }
+
+    /// Directory holding the todo file, as configured in `data_path`
+    pub fn data_dir(&self) -> PathBuf {
+        self.path().join("data").join("unsafeToDo")
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.path().join("config").join("unsafeToDo").join("config.json")
+    }
+
+    /// Write a config file with the given `auto_sync_enabled` flag
+    pub fn write_config(&self, auto_sync: bool) {
+        let config = serde_json::json!({
+            "data_path": self.data_dir(),
+            "auto_sync_enabled": auto_sync,
+            "git_remote": null,
+            "sort_order": ["CreatedDesc"],
+        });
+        fs::create_dir_all(self.config_file().parent().unwrap()).unwrap();
+        fs::write(self.config_file(), config.to_string()).unwrap();
+    }
+
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_unsafetodo"));
+        self.apply_env(&mut cmd);
+        cmd
+    }
+
+    pub fn utodo(&self, args: &[&str]) -> Output {
+        self.command().args(args).output().expect("failed to run utodo")
+    }
+
+    pub fn git(&self, dir: &Path, args: &[&str]) -> Output {
+        let mut cmd = Command::new("git");
+        self.apply_env(&mut cmd);
+        let output = cmd.arg("-C").arg(dir).args(args).output().unwrap();
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    fn apply_env(&self, cmd: &mut Command) {
+        cmd.env("HOME", self.path())
+            .env("XDG_CONFIG_HOME", self.path().join("config"))
+            .env("XDG_DATA_HOME", self.path().join("data"))
+            .env("COLUMNS", "160")
+            .env("GIT_AUTHOR_NAME", "utodo test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "utodo test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .env("GIT_CONFIG_NOSYSTEM", "1");
+    }
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+use std::path::Path;
+
+/// Ask for completions the way the fish script does, with the word being completed last
+fn complete(env: &TestEnv, words: &[&str]) -> String {
+    complete_in(env, env.path(), words)
+}
+
+fn complete_in(env: &TestEnv, dir: &Path, words: &[&str]) -> String {
+    let output = env
+        .command()
+        .current_dir(dir)
+        .env("COMPLETE", "fish")
+        .arg("--")
+        .arg("utodo")
+        .args(words)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    stdout(&output)
+}
+
+/// Candidates that are not flags
+fn values(candidates: String) -> String {
+    candidates
+        .lines()
+        .filter(|line| !line.starts_with('-'))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[test]
+fn completions_print_a_script_for_each_shell() {
+    let env = TestEnv::new();
+    for shell in ["bash", "zsh", "fish", "elvish", "powershell"] {
+        let output = env.utodo(&["completions", shell]);
+        assert!(output.status.success(), "{}: {}", shell, stderr(&output));
+        let script = stdout(&output);
+        assert!(script.contains("COMPLETE"), "{}", script);
+        assert!(script.contains("utodo"), "{}", script);
+    }
+    assert!(!env.utodo(&["completions", "tcsh"]).status.success());
+}
+
+#[test]
+fn todo_ids_complete_with_their_titles() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for title in ["write docs", "ship release"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+    assert!(env.utodo(&["complete", "0"]).status.success());
+
+    assert_eq!(values(complete(&env, &["complete", ""])), "1\tship release\n");
+    assert_eq!(values(complete(&env, &["remove", ""])), "0\twrite docs\n1\tship release\n");
+    assert_eq!(values(complete(&env, &["update", "--id", ""])), "0\twrite docs\n1\tship release\n");
+    assert!(complete(&env, &["sh"]).contains("show"));
+}
+
+#[test]
+fn typed_list_flags_select_the_completed_todos() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for title in ["global task", "another global task"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+    assert!(env.utodo(&["list", "create", "work"]).status.success());
+    assert!(env.utodo(&["--list", "work", "add", "--title", "work task"]).status.success());
+
+    assert_eq!(values(complete(&env, &["--list", "work", "remove", ""])), "0\twork task\n");
+    assert_eq!(values(complete(&env, &["remove", "--list=work", ""])), "0\twork task\n");
+    // A flag after the completed word does not apply yet, so the default list with two todos is used
+    let output = env
+        .command()
+        .env("COMPLETE", "bash")
+        .env("_CLAP_COMPLETE_INDEX", "2")
+        .args(["--", "utodo", "remove", "", "--list", "work"])
+        .output()
+        .unwrap();
+    assert_eq!(values(stdout(&output)), "0\n1\n");
+
+    let project = env.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    assert!(env.command().args(["init", "--local"]).current_dir(&project).output().unwrap().status.success());
+    assert_eq!(values(complete_in(&env, &project, &["remove", ""])), "");
+    assert_eq!(
+        values(complete_in(&env, &project, &["--global", "remove", ""])),
+        "0\tglobal task\n1\tanother global task\n"
+    );
+}
+
+#[test]
+fn completing_without_a_config_does_not_create_one() {
+    let env = TestEnv::new();
+    assert_eq!(values(complete(&env, &["remove", ""])), "");
+    assert!(!env.config_file().exists());
+}
+
+#[test]
+fn only_regular_commands_create_the_default_config() {
+    let env = TestEnv::new();
+    assert!(env.utodo(&["man"]).status.success());
+    assert!(env.utodo(&["completions", "bash"]).status.success());
+    assert!(!env.config_file().exists());
+
+    let output = env.utodo(&["list"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Created default config"), "{}", stdout(&output));
+    assert!(env.config_file().exists());
+
+    let output = env.utodo(&["list"]);
+    assert!(!stdout(&output).contains("Created default config"), "{}", stdout(&output));
+}
+
+#[test]
+fn man_prints_or_writes_pages() {
+    let env = TestEnv::new();
+    let output = env.utodo(&["man"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains(".TH utodo"), "{}", stdout(&output));
+
+    let dir = env.path().join("man");
+    let output = env.utodo(&["man", "--dir", dir.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.join("utodo.1").exists());
+    assert!(dir.join("utodo-add.1").exists());
+    assert!(std::fs::read_to_string(dir.join("utodo-remind.1")).unwrap().contains("interval"));
+}
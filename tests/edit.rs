@@ -0,0 +1,103 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fake editor running the given shell commands on the document in `$1`
+fn editor_script(env: &TestEnv, commands: &str) -> PathBuf {
+    let path = env.path().join("editor.sh");
+    fs::write(&path, format!("#!/bin/sh\n{}\n", commands)).unwrap();
+    std::process::Command::new("chmod").arg("+x").arg(&path).status().unwrap();
+    path
+}
+
+#[test]
+fn edit_applies_fields_and_description_and_shows_diff() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "draft", "--due-date", "01.01.2099"]).status.success());
+
+    let editor = editor_script(
+        &env,
+        "sed -i -e 's/^title: .*/title: final/' -e 's/^priority: .*/priority: High/' \
+         -e 's/^due: .*/due: /' -e 's/^tags: .*/tags: docs, release/' \"$1\"\n\
+         printf 'Line one\\n\\nLine two\\n' >> \"$1\"",
+    );
+    let output = env
+        .command()
+        .args(["edit", "0"])
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let diff = stdout(&output);
+    assert!(diff.contains("- title: draft") && diff.contains("+ title: final"), "{}", diff);
+    assert!(diff.contains("+ Line two"), "{}", diff);
+
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(stored.contains("\"final\"") && stored.contains("Line one\\n\\nLine two"), "{}", stored);
+    assert!(stored.contains("\"High\"") && stored.contains("release"), "{}", stored);
+    assert!(!stored.contains("2099"), "{}", stored);
+}
+
+#[test]
+fn invalid_document_is_rejected_and_add_edit_uses_template() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    let editor = editor_script(&env, "sed -i 's/^priority: .*/priority: urgent/' \"$1\"");
+    let output = env
+        .command()
+        .args(["add", "--title", "x", "--edit"])
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Invalid priority: urgent"), "{}", stderr(&output));
+    assert!(!env.data_dir().join("todos.json").exists());
+
+    let editor = editor_script(&env, "sed -i 's/^title: .*/title: from editor/' \"$1\"");
+    let output = env
+        .command()
+        .args(["add", "--edit"])
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(stored.contains("from editor"), "{}", stored);
+}
+
+#[test]
+fn document_is_edited_in_a_private_temporary_file() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "draft"]).status.success());
+    let temp_dir = env.path().join("tmp");
+    fs::create_dir_all(&temp_dir).unwrap();
+    let record = env.path().join("record");
+
+    let editor = editor_script(
+        &env,
+        &format!("printf '%s %s' \"$1\" \"$(stat -c %a \"$1\")\" > '{}'", record.display()),
+    );
+    let output = env
+        .command()
+        .args(["edit", "0"])
+        .env("TMPDIR", &temp_dir)
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let record = fs::read_to_string(record).unwrap();
+    let (path, mode) = record.rsplit_once(' ').unwrap();
+    assert!(path.starts_with(temp_dir.to_str().unwrap()) && path.ends_with(".md"), "{}", record);
+    assert_eq!(mode, "600");
+    assert!(fs::read_dir(&temp_dir).unwrap().next().is_none(), "temporary file left behind");
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{TestEnv, stderr};
+use serde_json::{Value, json};
+use std::fs;
+
+#[test]
+fn error_categories_have_distinct_exit_codes() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "first"]).status.success());
+
+    let output = env.utodo(&["complete", "7"]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Todo with ID 7 not found"));
+
+    let output = env.utodo(&["workflow", "only"]);
+    assert_eq!(output.status.code(), Some(4), "{}", stderr(&output));
+
+    let todos = env.data_dir().join("todos.json");
+    let mut content = fs::read_to_string(&todos).unwrap();
+    content.push_str("{ not json\n");
+    fs::write(&todos, content).unwrap();
+    let output = env.utodo(&["show"]);
+    assert_eq!(output.status.code(), Some(6), "{}", stderr(&output));
+    assert!(stderr(&output).contains("todos.json is corrupt at line 2"), "{}", stderr(&output));
+
+    fs::write(env.config_file(), "{").unwrap();
+    let output = env.utodo(&["show"]);
+    assert_eq!(output.status.code(), Some(8), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Invalid config file"), "{}", stderr(&output));
+}
+
+#[test]
+fn io_sync_hook_and_conflict_errors_have_their_own_exit_codes() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "first"]).status.success());
+
+    let output = env.command().env("EDITOR", "false").args(["edit", "0"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(5), "{}", stderr(&output));
+    assert!(stderr(&output).contains("exited with an error"), "{}", stderr(&output));
+
+    let not_a_repo = env.path().join("plain");
+    fs::create_dir_all(&not_a_repo).unwrap();
+    let output = env.utodo(&["hook", "install", "--repo", not_a_repo.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(7), "{}", stderr(&output));
+
+    assert!(env.utodo(&["start", "0"]).status.success());
+    let output = env.utodo(&["start", "0"]);
+    assert_eq!(output.status.code(), Some(9), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Timer already running"), "{}", stderr(&output));
+
+    assert!(env.utodo(&["list", "create", "work"]).status.success());
+    let output = env.utodo(&["list", "create", "work"]);
+    assert_eq!(output.status.code(), Some(9), "{}", stderr(&output));
+
+    let output = env.utodo(&["view", "delete", "next"]);
+    assert_eq!(output.status.code(), Some(8), "{}", stderr(&output));
+
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["hooks"] = json!({ "pre_add": "exit 1" });
+    fs::write(env.config_file(), config.to_string()).unwrap();
+    let output = env.utodo(&["add", "--title", "rejected"]);
+    assert_eq!(output.status.code(), Some(10), "{}", stderr(&output));
+}
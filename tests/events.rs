@@ -0,0 +1,265 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+fn set_hooks(env: &TestEnv, hooks: Value) {
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["hooks"] = hooks;
+    fs::write(env.config_file(), config.to_string()).unwrap();
+}
+
+fn stored_todos(env: &TestEnv) -> Vec<Value> {
+    fs::read_to_string(env.data_dir().join("todos.json"))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+/// Shell command appending the event, list and stdin of a hook to `log`
+fn log_command(log: &Path) -> String {
+    format!("{{ echo \"$UTODO_EVENT $UTODO_LIST\"; cat; echo; }} >> '{}'", log.display())
+}
+
+#[test]
+fn hooks_see_lifecycle_events_with_the_todo_on_stdin() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let log = env.path().join("events.log");
+    set_hooks(
+        &env,
+        json!({
+            "on_add": log_command(&log),
+            "on_update": log_command(&log),
+            "on_complete": log_command(&log),
+            "on_remove": log_command(&log),
+        }),
+    );
+
+    for args in [
+        vec!["add", "--title", "write hooks"],
+        vec!["update", "--id", "0", "--title", "write more hooks"],
+        vec!["complete", "0"],
+        vec!["remove", "0"],
+    ] {
+        let output = env.utodo(&args);
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    let log = fs::read_to_string(&log).unwrap();
+    let events: Vec<&str> = log.lines().step_by(2).collect();
+    assert_eq!(events, ["on_add default", "on_update default", "on_complete default", "on_remove default"]);
+    let todos: Vec<Value> = log.lines().skip(1).step_by(2).map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(todos[0]["title"], "write hooks");
+    assert_eq!(todos[1]["title"], "write more hooks");
+    assert_eq!(todos[2]["finished"], true);
+    assert_eq!(todos[3]["id"], 0);
+}
+
+#[test]
+fn scan_time_tracking_trash_and_archive_run_the_hooks() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let log = env.path().join("events.log");
+    set_hooks(
+        &env,
+        json!({
+            "on_add": log_command(&log),
+            "on_update": log_command(&log),
+            "on_complete": log_command(&log),
+            "on_remove": log_command(&log),
+            "on_archive": log_command(&log),
+            "on_unarchive": log_command(&log),
+        }),
+    );
+    let project = env.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("main.rs"), "// TODO: scanned\n").unwrap();
+
+    let project = project.to_str().unwrap();
+    for args in [
+        vec!["scan", project],
+        vec!["add", "--title", "timed"],
+        vec!["start", "1"],
+        vec!["stop"],
+        vec!["log", "1", "30m"],
+        vec!["remove", "1"],
+        vec!["trash", "restore", "0"],
+    ] {
+        let output = env.utodo(&args);
+        assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    }
+    fs::remove_file(env.path().join("project").join("main.rs")).unwrap();
+    for args in [vec!["scan", project], vec!["archive"], vec!["unarchive", "0"]] {
+        let output = env.utodo(&args);
+        assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    }
+
+    let log = fs::read_to_string(&log).unwrap();
+    let events: Vec<&str> = log.lines().step_by(2).collect();
+    assert_eq!(
+        events,
+        [
+            "on_add default",
+            "on_add default",
+            "on_update default",
+            "on_update default",
+            "on_update default",
+            "on_remove default",
+            "on_add default",
+            "on_complete default",
+            "on_archive default",
+            "on_unarchive default",
+        ]
+    );
+    let todos: Vec<Value> = log.lines().skip(1).step_by(2).map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(todos[4]["time_entries"].as_array().unwrap().len(), 2, "{}", todos[4]);
+    assert_eq!(todos[6]["title"], "timed");
+    assert_eq!(todos[8]["finished"], true);
+}
+
+#[test]
+fn finishing_a_todo_by_move_update_or_edit_is_a_complete_event() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for title in ["moved", "updated", "edited"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+    let log = env.path().join("events.log");
+    set_hooks(&env, json!({ "on_update": log_command(&log), "on_complete": log_command(&log) }));
+
+    for args in [
+        vec!["move", "0", "doing"],
+        vec!["move", "0", "done"],
+        vec!["update", "--id", "1", "--finished", "true"],
+        vec!["update", "--id", "1", "--title", "still updated"],
+    ] {
+        let output = env.utodo(&args);
+        assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    }
+    let output = env
+        .command()
+        .env("EDITOR", "sed -i 's/^state: todo$/state: done/'")
+        .args(["edit", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let log = fs::read_to_string(&log).unwrap();
+    let events: Vec<&str> = log.lines().step_by(2).collect();
+    assert_eq!(
+        events,
+        ["on_update default", "on_complete default", "on_complete default", "on_update default", "on_complete default"]
+    );
+}
+
+#[test]
+fn pre_hooks_can_reject_time_tracking_and_archiving() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "done"]).status.success());
+    assert!(env.utodo(&["complete", "0"]).status.success());
+    set_hooks(&env, json!({ "pre_update": "exit 1", "pre_archive": "exit 1" }));
+
+    assert_eq!(env.utodo(&["start", "0"]).status.code(), Some(10));
+    assert_eq!(env.utodo(&["log", "0", "1h"]).status.code(), Some(10));
+    let entries = &stored_todos(&env)[0]["time_entries"];
+    assert!(entries.is_null() || *entries == json!([]), "{}", entries);
+
+    assert_eq!(env.utodo(&["archive"]).status.code(), Some(10));
+    assert_eq!(stored_todos(&env).len(), 1);
+    assert!(!env.data_dir().join("archive").join("todos.json").exists());
+}
+
+#[test]
+fn pre_hooks_can_modify_or_reject_changes() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    set_hooks(
+        &env,
+        json!({
+            "pre_add": "sed 's/\"priority\":\"Low\"/\"priority\":\"High\"/'",
+            "pre_complete": "echo 'not on fridays' >&2; exit 1",
+            "pre_update": "echo '{not json'",
+        }),
+    );
+
+    assert!(env.utodo(&["add", "--title", "triage"]).status.success());
+    assert_eq!(stored_todos(&env)[0]["priority"], "High");
+
+    let output = env.utodo(&["complete", "0"]);
+    assert_eq!(output.status.code(), Some(10));
+    assert!(stderr(&output).contains("pre_complete hook failed: not on fridays"), "{}", stderr(&output));
+    assert_eq!(stored_todos(&env)[0]["finished"], false);
+
+    let output = env.utodo(&["update", "--id", "0", "--title", "renamed"]);
+    assert_eq!(output.status.code(), Some(10));
+    assert!(stderr(&output).contains("pre_update hook printed an invalid todo"), "{}", stderr(&output));
+    assert_eq!(stored_todos(&env)[0]["title"], "triage");
+}
+
+#[test]
+fn hooks_are_killed_after_the_timeout_and_on_hook_failures_only_warn() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    set_hooks(
+        &env,
+        json!({ "pre_remove": "sleep 5", "on_add": "exit 3", "timeout_seconds": 1 }),
+    );
+
+    let output = env.utodo(&["add", "--title", "keep me"]);
+    assert!(output.status.success());
+    assert!(stderr(&output).contains("Warning: on_add hook failed with exit status: 3"), "{}", stderr(&output));
+    assert_eq!(stored_todos(&env).len(), 1);
+
+    let started = Instant::now();
+    let output = env.utodo(&["remove", "0"]);
+    assert!(started.elapsed() < Duration::from_secs(4));
+    assert_eq!(output.status.code(), Some(10));
+    assert!(stderr(&output).contains("pre_remove hook timed out after 1 seconds"), "{}", stderr(&output));
+    assert_eq!(stored_todos(&env).len(), 1);
+    assert!(!env.data_dir().join("trash.json").exists());
+}
+
+#[test]
+fn pre_sync_can_hold_back_a_sync_and_post_sync_sees_the_result() {
+    let env = TestEnv::new();
+    let remote = env.path().join("remote.git");
+    let seed = env.path().join("seed");
+    env.git(env.path(), &["init", "--bare", "-b", "main", remote.to_str().unwrap()]);
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), seed.to_str().unwrap()]);
+    fs::write(seed.join("todos.json"), "").unwrap();
+    env.git(&seed, &["add", "todos.json"]);
+    env.git(&seed, &["commit", "-m", "Initial commit"]);
+    env.git(&seed, &["push", "-u", "origin", "main"]);
+    fs::create_dir_all(env.data_dir().parent().unwrap()).unwrap();
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), env.data_dir().to_str().unwrap()]);
+    env.write_config(true);
+    let hold = env.path().join("hold");
+    let log = env.path().join("sync.log");
+    set_hooks(
+        &env,
+        json!({
+            "pre_sync": format!("test ! -e '{}' || {{ echo 'sync on hold' >&2; exit 1; }}", hold.display()),
+            "post_sync": format!("echo \"$UTODO_SYNC_RESULT\" >> '{}'", log.display()),
+        }),
+    );
+
+    fs::write(&hold, "").unwrap();
+    let output = env.utodo(&["add", "--title", "held back"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("pre_sync hook failed: sync on hold"), "{}", stderr(&output));
+    assert!(env.data_dir().join("sync_queue.json").exists());
+
+    fs::remove_file(&hold).unwrap();
+    let output = env.utodo(&["show"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("held back"));
+    assert!(!env.data_dir().join("sync_queue.json").exists());
+    assert_eq!(fs::read_to_string(&log).unwrap(), "failed\nok\n");
+    assert!(stdout(&env.git(&remote, &["show", "main:todos.json"])).contains("held back"));
+}
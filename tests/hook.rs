@@ -0,0 +1,33 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn commit_messages_close_and_reference_todos() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let project = env.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    env.git(&project, &["init", "-b", "main"]);
+
+    assert!(env.utodo(&["add", "--title", "first"]).status.success());
+    assert!(env.utodo(&["add", "--title", "second"]).status.success());
+
+    let output = env.utodo(&["hook", "install", "--repo", project.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.join(".git/hooks/post-commit").exists());
+
+    fs::write(project.join("a.txt"), "a").unwrap();
+    env.git(&project, &["add", "a.txt"]);
+    env.git(&project, &["commit", "-m", "Fix parser\n\ncloses utodo#0, refs utodo#1"]);
+    let hash = stdout(&env.git(&project, &["rev-parse", "HEAD"])).trim().to_string();
+
+    let todos = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    let lines: Vec<&str> = todos.lines().collect();
+    assert!(lines[0].contains("\"finished\":true") && lines[0].contains(&hash));
+    assert!(lines[1].contains("\"finished\":false") && lines[1].contains(&hash));
+
+    let again = env.utodo(&["hook", "install", "--repo", project.to_str().unwrap()]);
+    assert!(again.status.success(), "reinstalling our own hook should work");
+}
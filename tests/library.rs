@@ -0,0 +1,45 @@
+use unsafetodo::urgency::{self, UrgencyCoefficients};
+use unsafetodo::{Error, Priority, SortCriteria, TodoBuilder, Workflow, parse_filter, parse_id_selection, workflow};
+
+#[test]
+fn library_builds_sorts_and_filters_todos() {
+    // Passed in explicitly, so the user's config is never read
+    workflow::set_current(Workflow::default()).unwrap();
+    urgency::set_coefficients(UrgencyCoefficients::default()).unwrap();
+    assert!(workflow::set_current(Workflow::default()).is_err());
+
+    let todos = vec![
+        TodoBuilder::new().title("low").build().unwrap(),
+        TodoBuilder::new().title("high").priority(Priority::High).build().unwrap(),
+        TodoBuilder::new().title("done").priority(Priority::High).finished(true).build().unwrap(),
+    ];
+
+    let mut sorted = todos.clone();
+    sorted.sort_by(|a, b| a.compare_by_order(b, &[SortCriteria::Priority, SortCriteria::TitleAsc]));
+    let titles: Vec<String> = sorted.iter().map(|todo| todo.get_title()).collect();
+    assert_eq!(titles, ["done", "high", "low"]);
+
+    let filter = parse_filter("priority:high and not finished").unwrap();
+    let matching: Vec<String> = todos
+        .iter()
+        .filter(|todo| filter.matches(todo))
+        .map(|todo| todo.get_title())
+        .collect();
+    assert_eq!(matching, ["high"]);
+
+    let selection = parse_id_selection("9,1,4,7-9").unwrap();
+    assert_eq!(selection.resolve(10).unwrap(), [1, 4, 7, 8, 9]);
+    assert_eq!(selection.resolve(8).unwrap_err(), 8);
+    assert_eq!(parse_id_selection("0-18446744073709551615").unwrap().resolve(3).unwrap_err(), 3);
+
+    let urgent = TodoBuilder::new().title("urgent").priority(Priority::High).build().unwrap();
+    assert!(urgent.urgency() > todos[0].urgency());
+
+    let error: Error = TodoBuilder::new().title("x").state("nope".to_string()).build().unwrap_err();
+    assert!(matches!(error, Error::Parse(_)));
+    assert_eq!(error.to_string(), "Unknown workflow state 'nope'");
+
+    assert!(matches!(parse_filter("priority:urgent"), Err(Error::Parse(_))));
+    assert!(matches!(parse_id_selection("3-1"), Err(Error::Parse(_))));
+    assert!(matches!(Workflow::new(vec!["only".to_string()]), Err(Error::Parse(_))));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use serde_json::{Value, json};
+use std::fs;
+
+#[test]
+fn todos_are_stored_per_list() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    assert!(env.utodo(&["add", "--title", "home task"]).status.success());
+    assert!(env.utodo(&["list", "create", "work"]).status.success());
+    assert!(env.utodo(&["--list", "work", "add", "--title", "work task"]).status.success());
+
+    let default_list = stdout(&env.utodo(&["show"]));
+    assert!(default_list.contains("home task"));
+    assert!(!default_list.contains("work task"));
+
+    assert!(env.utodo(&["list", "switch", "work"]).status.success());
+    let work_list = stdout(&env.utodo(&["show"]));
+    assert!(work_list.contains("work task"));
+    assert!(!work_list.contains("home task"));
+
+    let all = stdout(&env.utodo(&["show", "--all-lists"]));
+    assert!(all.contains("home task") && all.contains("work task"));
+}
+
+#[test]
+fn default_list_cannot_be_deleted() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    assert!(env.utodo(&["list", "create", "work"]).status.success());
+    assert!(env.utodo(&["list", "switch", "work"]).status.success());
+
+    let output = env.utodo(&["list", "delete", "work"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("default list"));
+
+    assert!(env.utodo(&["list", "rename", "work", "job"]).status.success());
+    assert!(stdout(&env.utodo(&["list"])).contains("* job"));
+}
+
+#[test]
+fn list_names_cannot_leave_the_data_directory() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    let output = env.utodo(&["--list", "../../escaped", "add", "--title", "outside"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Invalid list name"), "{}", stderr(&output));
+    assert!(!env.path().join("escaped.json").exists());
+
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["default_list"] = json!("../../escaped");
+    fs::write(env.config_file(), config.to_string()).unwrap();
+    let output = env.utodo(&["add", "--title", "outside"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(stderr(&output).contains("Invalid default_list"), "{}", stderr(&output));
+}
+
+#[test]
+fn reading_todos_does_not_create_the_data_directory() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    let output = env.utodo(&["show"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!env.data_dir().exists());
+
+    assert!(env.utodo(&["add", "--title", "first"]).status.success());
+    assert!(env.data_dir().join("todos.json").exists());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{TestEnv, stdout};
+use std::fs;
+
+#[test]
+fn local_todos_are_found_from_subdirectories() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let project = env.path().join("project");
+    let nested = project.join("src").join("module");
+    fs::create_dir_all(&nested).unwrap();
+
+    assert!(env.utodo(&["add", "--title", "global task"]).status.success());
+
+    let init = env.command().args(["init", "--local"]).current_dir(&project).output().unwrap();
+    assert!(init.status.success());
+    assert!(project.join(".utodo").join("todos.json").exists());
+
+    let add = env.command().args(["add", "--title", "local task"]).current_dir(&nested).output().unwrap();
+    assert!(add.status.success());
+    let local_file = fs::read_to_string(project.join(".utodo").join("todos.json")).unwrap();
+    assert!(local_file.contains("local task"));
+
+    let show = stdout(&env.command().arg("show").current_dir(&nested).output().unwrap());
+    assert!(show.contains("local task"));
+    assert!(!show.contains("global task"));
+
+    let global = stdout(&env.command().args(["--global", "show"]).current_dir(&nested).output().unwrap());
+    assert!(global.contains("global task"));
+    assert!(!global.contains("local task"));
+
+    let both = stdout(
+        &env.command()
+            .args(["show", "--include-global"])
+            .current_dir(&nested)
+            .output()
+            .unwrap(),
+    );
+    assert!(both.contains("local:default") && both.contains("local task"));
+    assert!(both.contains("global:default") && both.contains("global task"));
+}
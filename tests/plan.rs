@@ -0,0 +1,42 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+
+#[test]
+fn plan_fills_capacity_by_priority_due_date_and_estimate() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let add = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+    };
+    add(&["add", "--title", "big low", "--estimate", "4h"]);
+    add(&["add", "--title", "urgent", "-p", "high", "--estimate", "3h"]);
+    add(&["add", "--title", "later high", "-p", "high", "--estimate", "2h", "--due-date", "01.01.2099"]);
+    add(&["add", "--title", "soon high", "-p", "high", "--estimate", "2h", "--due-date", "01.01.2098"]);
+    add(&["add", "--title", "quick", "--estimate", "30m"]);
+    add(&["add", "--title", "unknown"]);
+
+    let output = stdout(&env.utodo(&["plan", "--capacity", "6h"]));
+    let planned: Vec<&str> = output.lines().filter(|line| line.starts_with("  #")).collect();
+
+    // High priority first, earliest due date first, then whatever still fits
+    assert_eq!(planned.len(), 3, "{}", output);
+    assert!(planned[0].contains("soon high"));
+    assert!(planned[1].contains("later high"));
+    assert!(planned[2].contains("quick"));
+    assert!(output.contains("Planned 4h 30m of 6h"));
+    assert!(output.contains("1 open todos have no estimate"));
+}
+
+#[test]
+fn out_of_range_estimates_are_rejected() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for estimate in ["999999999999999h", "1000001h", "0m"] {
+        let output = env.utodo(&["add", "--title", "huge", "--estimate", estimate]);
+        assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+        assert!(stderr(&output).contains("Invalid duration"), "{}", stderr(&output));
+    }
+    assert!(env.utodo(&["add", "--title", "big", "--estimate", "1000000h"]).status.success());
+}
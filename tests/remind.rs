@@ -0,0 +1,138 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::TestEnv;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+fn set_notifier(env: &TestEnv, notifier: Value) {
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["notifier"] = notifier;
+    fs::write(env.config_file(), config.to_string()).unwrap();
+}
+
+fn remind_once(env: &TestEnv) -> String {
+    let output = env.utodo(&["remind", "--once"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn date(days_from_today: i64) -> String {
+    (Utc::now().date_naive() + Duration::days(days_from_today))
+        .format("%d.%m.%Y")
+        .to_string()
+}
+
+/// Read one request including its body, so closing the connection does not reset it
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    loop {
+        let read = stream.read(&mut buffer).unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .and_then(|length| length.parse().ok())
+                .unwrap_or(0);
+            if body.len() >= length || read == 0 {
+                return text;
+            }
+        }
+    }
+}
+
+#[test]
+fn remind_sends_due_overdue_and_scheduled_reminders_once() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let today = date(0);
+    let yesterday = date(-1);
+    for args in [
+        vec!["add", "--title", "due today", "--due-date", today.as_str()],
+        vec!["add", "--title", "overdue", "--due-date", yesterday.as_str()],
+        vec!["add", "--title", "later", "--due-date", "01.01.2999", "--remind", "01.01.2999 09:00"],
+        vec!["add", "--title", "call back", "--remind", "01.01.2020 09:00"],
+        vec!["add", "--title", "already done", "--due-date", yesterday.as_str()],
+    ] {
+        assert!(env.utodo(&args).status.success());
+    }
+    assert!(env.utodo(&["complete", "4"]).status.success());
+
+    let stdout = remind_once(&env);
+    assert!(stdout.contains("due today: #0 is due today"), "{}", stdout);
+    assert!(stdout.contains(&format!("overdue: #1 is overdue since {}", yesterday)), "{}", stdout);
+    assert!(stdout.contains("call back: Reminder for #3 set for 01.01.2020 09:00"), "{}", stdout);
+    assert!(!stdout.contains("later") && !stdout.contains("already done"), "{}", stdout);
+
+    // Sent reminders are recorded and not repeated
+    assert!(env.data_dir().join("reminders.json").exists());
+    assert_eq!(remind_once(&env), "");
+
+    // A new due date arms the reminder again
+    assert!(env.utodo(&["update", "--id", "0", "--due-date", yesterday.as_str()]).status.success());
+    assert!(remind_once(&env).contains("#0 is overdue"));
+
+    // Finishing everything forgets the sent reminders
+    assert!(env.utodo(&["complete", "0,1,3", "--yes"]).status.success());
+    assert_eq!(remind_once(&env), "");
+    assert!(!env.data_dir().join("reminders.json").exists());
+}
+
+#[test]
+fn remind_runs_the_configured_command() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let log = env.path().join("notifications.log");
+    set_notifier(
+        &env,
+        json!({ "type": "command", "command": format!("printf '%s|%s\\n' \"$1\" \"$2\" >> '{}'", log.display()) }),
+    );
+    let today = date(0);
+    assert!(env.utodo(&["add", "--title", "ship it", "--due-date", today.as_str()]).status.success());
+
+    assert_eq!(remind_once(&env), "");
+    assert_eq!(fs::read_to_string(&log).unwrap(), "ship it|#0 is due today\n");
+}
+
+#[test]
+fn remind_posts_to_the_webhook_and_retries_failures() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hooks/utodo", listener.local_addr().unwrap());
+    set_notifier(&env, json!({ "type": "webhook", "url": url }));
+    let yesterday = date(-1);
+    assert!(env.utodo(&["add", "--title", "pay rent", "--due-date", yesterday.as_str()]).status.success());
+
+    // Answer the first request with an error and the second one with success
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for status in ["500 Internal Server Error", "204 No Content"] {
+            let (mut stream, _) = listener.accept().unwrap();
+            requests.push(read_request(&mut stream));
+            write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+        }
+        requests
+    });
+
+    let failed = env.utodo(&["remind", "--once"]);
+    assert!(failed.status.success());
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("Failed to send reminder for 'pay rent'"));
+    assert!(!env.data_dir().join("reminders.json").exists());
+    remind_once(&env);
+    assert!(env.data_dir().join("reminders.json").exists());
+
+    let requests = server.join().unwrap();
+    assert!(requests[1].starts_with("POST /hooks/utodo HTTP/1.1"), "{}", requests[1]);
+    let (_, body) = requests[1].split_once("\r\n\r\n").unwrap();
+    let body: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["title"], "pay rent");
+    assert_eq!(body["kind"], "overdue");
+    assert_eq!(body["id"], 0);
+}
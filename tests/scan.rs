@@ -0,0 +1,68 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn scan_imports_and_reconciles_comments() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let project = env.path().join("project");
+    fs::create_dir_all(project.join("target")).unwrap();
+    fs::write(project.join(".gitignore"), "target/\n").unwrap();
+    fs::write(
+        project.join("main.rs"),
+        "fn main() {\n    // TODO: handle errors\n    let x = 1; // FIXME(bob): overflow\n}\n",
+    )
+    .unwrap();
+    fs::write(project.join("build.sh"), "# HACK pin the version\nTODO_FILE=1\n").unwrap();
+    fs::write(project.join("target").join("gen.rs"), "// TODO: generated\n").unwrap();
+
+    let output = env.utodo(&["scan", project.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("3 added, 0 updated, 0 finished"));
+
+    let todos = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(todos.contains("handle errors"));
+    assert!(todos.contains("overflow"));
+    assert!(todos.contains("pin the version"));
+    assert!(!todos.contains("generated"));
+
+    // Move one comment, remove another
+    fs::write(
+        project.join("main.rs"),
+        "use std::io;\n\nfn main() {\n    // TODO: handle errors\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+
+    let output = env.utodo(&["scan", project.to_str().unwrap()]);
+    assert!(stdout(&output).contains("0 added, 1 updated, 1 finished"));
+
+    let output = env.utodo(&["scan", project.to_str().unwrap()]);
+    assert!(stdout(&output).contains("0 added, 0 updated, 0 finished"));
+}
+
+#[test]
+fn source_paths_are_stored_relative_to_the_project() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let project = env.path().join("project");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src").join("main.rs"), "// TODO: relative\n").unwrap();
+    let init = env.command().args(["init", "--local"]).current_dir(&project).output().unwrap();
+    assert!(init.status.success(), "{}", stderr(&init));
+
+    let output = env.command().args(["scan", "src"]).current_dir(&project).output().unwrap();
+    assert!(stdout(&output).contains("1 added"), "{}", stderr(&output));
+    let todos = fs::read_to_string(project.join(".utodo").join("todos.json")).unwrap();
+    let todo: serde_json::Value = serde_json::from_str(todos.trim()).unwrap();
+    assert_eq!(todo["source"]["file"], "src/main.rs");
+
+    // A checkout elsewhere recognizes the same comments
+    let moved = env.path().join("moved");
+    fs::rename(&project, &moved).unwrap();
+    let output = env.command().arg("scan").current_dir(&moved).output().unwrap();
+    assert!(stdout(&output).contains("0 added, 0 updated, 0 finished"), "{}", stdout(&output));
+    let show = stdout(&env.command().arg("show").current_dir(moved.join("src")).output().unwrap());
+    assert!(show.contains("relative"), "{}", show);
+}
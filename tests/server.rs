@@ -0,0 +1,245 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Stdio};
+use std::time::Duration;
+
+/// A running `utodo serve`, stopped when dropped
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Server {
+    fn start(env: &TestEnv) -> Self {
+        let mut child = env
+            .command()
+            .args(["serve", "--bind", "127.0.0.1:0"])
+            // An open stdin nobody writes to, so a prompt would block the server
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap()).read_line(&mut line).unwrap();
+        let addr = line
+            .trim()
+            .strip_prefix("Listening on http://")
+            .unwrap_or_else(|| panic!("unexpected output: {}", line))
+            .to_string();
+        Server { child, addr }
+    }
+
+    /// Send a JSON request to the served address and return the status code and JSON body
+    fn request(&self, method: &str, path: &str, token: Option<&str>, body: Option<Value>) -> (u16, Value) {
+        let auth = token.map(|token| format!("Bearer {}", token));
+        let mut headers = vec![("Host", self.addr.as_str()), ("Content-Type", "application/json")];
+        if let Some(auth) = &auth {
+            headers.push(("Authorization", auth));
+        }
+        self.send(method, path, &headers, body)
+    }
+
+    /// Send a request with exactly the given headers
+    fn send(&self, method: &str, path: &str, headers: &[(&str, &str)], body: Option<Value>) -> (u16, Value) {
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect();
+        let mut stream = TcpStream::connect(&self.addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            method,
+            path,
+            headers,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+#[test]
+fn api_creates_lists_updates_completes_and_deletes_todos() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "from cli"]).status.success());
+    let server = Server::start(&env);
+
+    let (status, created) = server.request(
+        "POST",
+        "/todos",
+        None,
+        Some(json!({ "title": "from api", "priority": "High", "tags": ["dashboard"] })),
+    );
+    assert_eq!(status, 201, "{}", created);
+    assert_eq!(created["id"], 1);
+
+    let (status, todos) = server.request("GET", "/todos?where=priority%3Ahigh&sort=title-asc", None, None);
+    assert_eq!(status, 200);
+    assert_eq!(todos.as_array().unwrap().len(), 1, "{}", todos);
+    assert_eq!(todos[0]["title"], "from api");
+
+    let (status, updated) = server.request("PATCH", "/todos/1", None, Some(json!({ "state": "doing" })));
+    assert_eq!(status, 200, "{}", updated);
+    assert_eq!(updated["state"], "doing");
+
+    let (status, completed) = server.request("POST", "/todos/0/complete", None, None);
+    assert_eq!(status, 200);
+    assert_eq!(completed["finished"], true);
+
+    let (status, error) = server.request("GET", "/todos/9", None, None);
+    assert_eq!(status, 404);
+    assert_eq!(error["error"], "Todo with ID 9 not found");
+    let (status, _) = server.request("PATCH", "/todos/1", None, Some(json!({ "state": "nope" })));
+    assert_eq!(status, 400);
+
+    let (status, deleted) = server.request("DELETE", "/todos/0", None, None);
+    assert_eq!(status, 200);
+    assert_eq!(deleted["title"], "from cli");
+
+    // The CLI sees the changes made through the API
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(stored.contains("from api") && !stored.contains("from cli"), "{}", stored);
+    assert!(fs::read_to_string(env.data_dir().join("trash.json")).unwrap().contains("from cli"));
+}
+
+#[test]
+fn api_validates_estimates_and_archives_like_the_cli() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["auto_archive_days"] = json!(0);
+    fs::write(env.config_file(), config.to_string()).unwrap();
+    let server = Server::start(&env);
+
+    for minutes in [json!(0), json!(-30), json!(i64::MAX)] {
+        let (status, error) = server.request("POST", "/todos", None, Some(json!({ "title": "x", "estimate_minutes": minutes })));
+        assert_eq!(status, 400, "{}", error);
+        assert!(error["error"].as_str().unwrap().contains("estimate_minutes"), "{}", error);
+    }
+    let (status, created) = server.request("POST", "/todos", None, Some(json!({ "title": "sized", "estimate_minutes": 90 })));
+    assert_eq!(status, 201, "{}", created);
+    let (status, _) = server.request("PATCH", "/todos/0", None, Some(json!({ "estimate_minutes": i64::MIN })));
+    assert_eq!(status, 400);
+    let (status, _) = server.request("PATCH", "/todos/0", None, Some(json!({ "title": " " })));
+    assert_eq!(status, 400);
+
+    let (status, _) = server.request("POST", "/todos/0/complete", None, None);
+    assert_eq!(status, 200);
+    let (_, todos) = server.request("GET", "/todos", None, None);
+    assert_eq!(todos, json!([]));
+    let archived = fs::read_to_string(env.data_dir().join("archive").join("todos.json")).unwrap();
+    assert!(archived.contains("sized"), "{}", archived);
+}
+
+#[test]
+fn api_requires_the_configured_bearer_token() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let mut config: Value = serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["api_token"] = json!("secret");
+    fs::write(env.config_file(), config.to_string()).unwrap();
+    let server = Server::start(&env);
+
+    let (status, _) = server.request("GET", "/todos", None, None);
+    assert_eq!(status, 401);
+    let (status, _) = server.request("GET", "/todos", Some("wrong"), None);
+    assert_eq!(status, 401);
+    let (status, todos) = server.request("GET", "/todos", Some("secret"), None);
+    assert_eq!(status, 200);
+    assert_eq!(todos, json!([]));
+}
+
+#[test]
+fn api_rejects_cross_site_and_rebound_requests() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let server = Server::start(&env);
+    let port = server.addr.rsplit_once(':').unwrap().1.to_string();
+    let localhost = format!("localhost:{}", port);
+    let json = ("Content-Type", "application/json");
+
+    // A page posting a form or plain text cannot set a JSON content type without a preflight
+    let form = ("Content-Type", "application/x-www-form-urlencoded");
+    let (status, _) = server.send("POST", "/todos", &[("Host", &localhost), form], Some(json!({ "title": "x" })));
+    assert_eq!(status, 415);
+    let (status, _) = server.send("POST", "/todos/0/complete", &[("Host", &localhost)], None);
+    assert_eq!(status, 415);
+    let (status, _) = server.send("GET", "/todos", &[("Host", &localhost)], None);
+    assert_eq!(status, 200);
+
+    // A rebound DNS name still carries the attacker's host name
+    let rebound = format!("attacker.example:{}", port);
+    let (status, _) = server.send("GET", "/todos", &[("Host", &rebound), json], None);
+    assert_eq!(status, 403);
+    let (status, _) = server.send("GET", "/todos", &[("Host", "localhost:1"), json], None);
+    assert_eq!(status, 403);
+    let (status, _) = server.send("GET", "/todos", &[json], None);
+    assert_eq!(status, 403);
+    let (status, _) = server.send("GET", "/todos", &[("Host", &format!("[::1]:{}", port)), json], None);
+    assert_eq!(status, 200);
+
+    let (status, created) = server.send("POST", "/todos", &[("Host", &localhost), json], Some(json!({ "title": "x" })));
+    assert_eq!(status, 201, "{}", created);
+}
+
+#[test]
+fn serving_beyond_loopback_needs_a_token() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    for bind in ["0.0.0.0:0", "127.0.0.1.example.invalid:0", "not an address"] {
+        let output = env.utodo(&["serve", "--bind", bind]);
+        assert_eq!(output.status.code(), Some(8), "{}", bind);
+        assert!(stderr(&output).contains("api_token"), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn sync_conflicts_while_serving_keep_both_versions_without_prompting() {
+    let env = TestEnv::new();
+    let remote = env.path().join("remote.git");
+    let seed = env.path().join("seed");
+    env.git(env.path(), &["init", "--bare", "-b", "main", remote.to_str().unwrap()]);
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), seed.to_str().unwrap()]);
+    fs::write(seed.join("todos.json"), "").unwrap();
+    env.git(&seed, &["add", "todos.json"]);
+    env.git(&seed, &["commit", "-m", "Initial commit"]);
+    env.git(&seed, &["push", "-u", "origin", "main"]);
+    fs::create_dir_all(env.data_dir().parent().unwrap()).unwrap();
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), env.data_dir().to_str().unwrap()]);
+    env.write_config(true);
+    assert!(env.utodo(&["add", "--title", "shared"]).status.success());
+
+    env.git(&seed, &["pull"]);
+    let todos = fs::read_to_string(seed.join("todos.json")).unwrap();
+    fs::write(seed.join("todos.json"), todos.replace("shared", "renamed elsewhere")).unwrap();
+    env.git(&seed, &["commit", "-am", "Rename"]);
+    env.git(&seed, &["push"]);
+
+    let server = Server::start(&env);
+    let (status, updated) = server.request("PATCH", "/todos/0", None, Some(json!({ "title": "renamed here" })));
+    assert_eq!(status, 200, "{}", updated);
+    let pushed = stdout(&env.git(&remote, &["show", "main:todos.json"]));
+    assert!(pushed.contains("renamed here") && pushed.contains("renamed elsewhere"), "{}", pushed);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn show_applies_sort_order_without_rewriting_storage() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let run = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    run(&["add", "--title", "alpha", "-p", "low"]);
+    run(&["add", "--title", "bravo", "-p", "high"]);
+    run(&["sort", "priority"]);
+    // Added after sorting, still shown in the configured order
+    run(&["add", "--title", "charlie", "-p", "medium"]);
+
+    let output = run(&["show"]);
+    let position = |output: &str, title: &str| output.find(title).unwrap();
+    assert!(position(&output, "bravo") < position(&output, "charlie"), "{}", output);
+    assert!(position(&output, "charlie") < position(&output, "alpha"), "{}", output);
+
+    let output = run(&["show", "--sort", "title-desc"]);
+    assert!(position(&output, "charlie") < position(&output, "bravo"), "{}", output);
+    assert!(position(&output, "bravo") < position(&output, "alpha"), "{}", output);
+
+    // The stored order, and thus the IDs, are unchanged
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(position(&stored, "alpha") < position(&stored, "bravo"));
+    assert!(position(&stored, "bravo") < position(&stored, "charlie"));
+    let config = fs::read_to_string(env.config_file()).unwrap();
+    assert!(config.contains("\"Priority\""), "{}", config);
+}
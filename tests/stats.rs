@@ -0,0 +1,30 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+
+#[test]
+fn stats_counts_open_finished_and_overdue() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    assert!(env.utodo(&["add", "--title", "a", "--priority", "high"]).status.success());
+    assert!(env.utodo(&["add", "--title", "b", "--due-date", "01.01.2020"]).status.success());
+    assert!(env.utodo(&["add", "--title", "c"]).status.success());
+    assert!(env.utodo(&["complete", "0"]).status.success());
+
+    let output = env.utodo(&["stats", "--json"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stats: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+
+    assert_eq!(stats["total"], 3);
+    assert_eq!(stats["open"], 2);
+    assert_eq!(stats["finished"], 1);
+    assert_eq!(stats["overdue"], 1);
+    assert_eq!(stats["open_by_priority"]["high"], 0);
+    assert_eq!(stats["open_by_priority"]["low"], 2);
+    let weeks = stats["completions_per_week"].as_array().unwrap();
+    assert_eq!(weeks.last().unwrap()["completed"], 1);
+
+    let output = env.utodo(&["stats", "--until", "01.01.2000"]);
+    assert!(stdout(&output).contains("Todos:    0"));
+}
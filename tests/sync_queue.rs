@@ -0,0 +1,63 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Set up a bare remote and clone it as the data directory
+fn setup_synced_env() -> (TestEnv, PathBuf) {
+    let env = TestEnv::new();
+    let remote = env.path().join("remote.git");
+    let seed = env.path().join("seed");
+
+    env.git(env.path(), &["init", "--bare", "-b", "main", remote.to_str().unwrap()]);
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), seed.to_str().unwrap()]);
+    fs::write(seed.join("todos.json"), "").unwrap();
+    env.git(&seed, &["add", "todos.json"]);
+    env.git(&seed, &["commit", "-m", "Initial commit"]);
+    env.git(&seed, &["push", "-u", "origin", "main"]);
+
+    fs::create_dir_all(env.data_dir().parent().unwrap()).unwrap();
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), env.data_dir().to_str().unwrap()]);
+    env.write_config(true);
+    (env, remote)
+}
+
+fn remote_todos(env: &TestEnv, remote: &Path) -> String {
+    let output = env.git(remote, &["show", "main:todos.json"]);
+    stdout(&output)
+}
+
+#[test]
+fn sync_pushes_to_reachable_remote() {
+    let (env, remote) = setup_synced_env();
+
+    let output = env.utodo(&["add", "--title", "online"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert!(remote_todos(&env, &remote).contains("online"));
+    assert!(!env.data_dir().join("sync_queue.json").exists());
+    assert!(!stdout(&env.utodo(&["show"])).contains("not yet synced"));
+}
+
+#[test]
+fn failed_sync_is_queued_and_retried() {
+    let (env, remote) = setup_synced_env();
+    let offline = env.path().join("offline.git");
+
+    fs::rename(&remote, &offline).unwrap();
+    let output = env.utodo(&["add", "--title", "offline"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Git sync failed"));
+    assert!(env.data_dir().join("sync_queue.json").exists());
+
+    let output = env.utodo(&["show"]);
+    assert!(stdout(&output).contains("1 change not yet synced"));
+
+    fs::rename(&offline, &remote).unwrap();
+    let output = env.utodo(&["show"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("not yet synced"));
+    assert!(!env.data_dir().join("sync_queue.json").exists());
+    assert!(remote_todos(&env, &remote).contains("offline"));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+
+#[test]
+fn only_one_timer_runs_at_a_time() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "first"]).status.success());
+    assert!(env.utodo(&["add", "--title", "second"]).status.success());
+
+    assert!(env.utodo(&["start", "0"]).status.success());
+    let output = env.utodo(&["start", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("already running on #0 first"));
+    assert!(stdout(&env.utodo(&["show"])).contains("Timer running on #0 first"));
+
+    let output = env.utodo(&["stop"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Timer stopped on #0 first"));
+    assert!(!env.utodo(&["stop"]).status.success());
+    assert!(env.utodo(&["start", "1"]).status.success());
+}
+
+#[test]
+fn timesheet_totals_logged_time() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "billing"]).status.success());
+    assert!(env.utodo(&["add", "--title", "meeting, weekly"]).status.success());
+
+    assert!(env.utodo(&["log", "0", "1h30m", "--date", "06.01.2025"]).status.success());
+    assert!(env.utodo(&["log", "0", "30m", "--date", "07.01.2025"]).status.success());
+    assert!(env.utodo(&["log", "1", "1d", "--date", "07.01.2025"]).status.success());
+    assert!(!env.utodo(&["log", "1", "soon"]).status.success());
+
+    let text = stdout(&env.utodo(&["timesheet", "--by", "week"]));
+    assert!(text.contains("2025-W02  10h"));
+    assert!(text.contains("Total  10h"));
+
+    let csv = stdout(&env.utodo(&["timesheet", "--csv", "--since", "07.01.2025"]));
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "period,list,id,title,minutes,hours");
+    assert_eq!(lines[1], "2025-01-07,default,0,billing,30,0.50");
+    assert_eq!(lines[2], "2025-01-07,default,1,\"meeting, weekly\",480,8.00");
+    assert_eq!(lines.len(), 3);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn removed_todos_go_to_the_trash_and_can_be_restored() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let run = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    for title in ["keep", "oops", "gone"] {
+        run(&["add", "--title", title]);
+    }
+
+    let output = run(&["remove", "1-2", "--yes"]);
+    assert!(output.contains("Moved todo with ID 1 'oops' to the trash"), "{}", output);
+    let listed = run(&["trash", "list"]);
+    assert!(listed.contains("oops (from default") && listed.contains("gone"), "{}", listed);
+
+    let index = listed.lines().position(|line| line.contains("oops")).unwrap();
+    let output = run(&["trash", "restore", &index.to_string()]);
+    assert!(output.contains("Restored 'oops' to list 'default' as todo with ID 1"), "{}", output);
+    let stored = fs::read_to_string(env.data_dir().join("todos.json")).unwrap();
+    assert!(stored.contains("oops") && !stored.contains("gone"), "{}", stored);
+
+    assert!(run(&["trash", "empty"]).contains("Deleted 1 todos"));
+    assert!(run(&["trash"]).contains("The trash is empty"));
+    assert!(!env.data_dir().join("trash.json").exists());
+}
+
+#[test]
+fn trash_purges_todos_after_the_retention_period() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "old"]).status.success());
+    assert!(env.utodo(&["add", "--title", "recent"]).status.success());
+    assert!(env.utodo(&["remove", "0-1", "--yes"]).status.success());
+
+    // Pretend "old" was removed long ago
+    let path = env.data_dir().join("trash.json");
+    let mut trash: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    let entries = trash["entries"].as_array_mut().unwrap();
+    let old = entries.iter_mut().find(|entry| entry["todo"]["title"] == "old").unwrap();
+    old["deleted_at"] = serde_json::json!("2000-01-01T00:00:00Z");
+    fs::write(&path, trash.to_string()).unwrap();
+
+    let listed = stdout(&env.utodo(&["trash", "list"]));
+    assert!(!listed.contains("old") && listed.contains("recent"), "{}", listed);
+    assert!(!fs::read_to_string(&path).unwrap().contains("\"old\""));
+}
+
+#[test]
+fn restoring_into_a_corrupt_list_fails_instead_of_using_the_active_list() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["list", "create", "work"]).status.success());
+    assert!(env.utodo(&["--list", "work", "add", "--title", "work task"]).status.success());
+    assert!(env.utodo(&["--list", "work", "remove", "0"]).status.success());
+    let work = env.data_dir().join("lists").join("work.json");
+    fs::write(&work, "{not json\n").unwrap();
+
+    assert_eq!(env.utodo(&["trash", "restore", "0"]).status.code(), Some(6));
+    assert!(stdout(&env.utodo(&["trash"])).contains("work task"));
+    assert!(!fs::read_to_string(env.data_dir().join("todos.json")).unwrap_or_default().contains("work task"));
+
+    // A deleted list falls back to the active one
+    fs::remove_file(&work).unwrap();
+    let output = env.utodo(&["trash", "restore", "0"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("to list 'default'"), "{}", stdout(&output));
+}
+
+#[test]
+fn sync_conflicts_in_the_trash_keep_the_entries_of_both_sides() {
+    let env = TestEnv::new();
+    let remote = env.path().join("remote.git");
+    let seed = env.path().join("seed");
+    env.git(env.path(), &["init", "--bare", "-b", "main", remote.to_str().unwrap()]);
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), seed.to_str().unwrap()]);
+    fs::write(seed.join("todos.json"), "").unwrap();
+    env.git(&seed, &["add", "todos.json"]);
+    env.git(&seed, &["commit", "-m", "Initial commit"]);
+    env.git(&seed, &["push", "-u", "origin", "main"]);
+    fs::create_dir_all(env.data_dir().parent().unwrap()).unwrap();
+    env.git(env.path(), &["clone", remote.to_str().unwrap(), env.data_dir().to_str().unwrap()]);
+    env.write_config(true);
+
+    for title in ["removed here", "removed there"] {
+        assert!(env.utodo(&["add", "--title", title]).status.success());
+    }
+    env.git(&seed, &["pull"]);
+
+    // Remove one todo while offline and the other one from the second clone
+    let offline = env.path().join("offline.git");
+    fs::rename(&remote, &offline).unwrap();
+    assert!(env.utodo(&["remove", "0"]).status.success());
+    fs::rename(&offline, &remote).unwrap();
+    let local: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(env.data_dir().join("trash.json")).unwrap()).unwrap();
+    let mut other = local.clone();
+    let seed_todos = fs::read_to_string(seed.join("todos.json")).unwrap();
+    other["entries"][0]["todo"] = serde_json::from_str(seed_todos.lines().nth(1).unwrap()).unwrap();
+    fs::write(seed.join("trash.json"), serde_json::to_string_pretty(&other).unwrap()).unwrap();
+    env.git(&seed, &["add", "trash.json"]);
+    env.git(&seed, &["commit", "-m", "Remove a todo"]);
+    env.git(&seed, &["push"]);
+
+    let output = env.utodo(&["trash"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let listed = stdout(&output);
+    assert!(listed.contains("removed here") && listed.contains("removed there"), "{}", listed);
+    assert!(!listed.contains("not yet synced"), "{}", listed);
+    let pushed = stdout(&env.git(&remote, &["show", "main:trash.json"]));
+    assert!(pushed.contains("removed here") && pushed.contains("removed there"), "{}", pushed);
+}
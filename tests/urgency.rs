@@ -0,0 +1,33 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+
+#[test]
+fn sort_by_urgency_weighs_priority_due_date_and_tags() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["urgency"] = serde_json::json!({ "tags": { "bug": 10.0 } });
+    std::fs::write(env.config_file(), config.to_string()).unwrap();
+
+    let add = |args: &[&str]| {
+        let output = env.utodo(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+    };
+    add(&["add", "--title", "someday"]);
+    add(&["add", "--title", "important", "-p", "high"]);
+    add(&["add", "--title", "overdue", "--due-date", "01.01.2000"]);
+    add(&["add", "--title", "tagged", "--tag", "bug"]);
+
+    let output = env.utodo(&["sort", "urgency"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = stdout(&env.utodo(&["show"]));
+    let position = |title: &str| output.find(title).unwrap_or_else(|| panic!("{} missing:\n{}", title, output));
+    // overdue: 12 + 4, tagged: 10, important: 6, someday: 0
+    assert!(position("overdue") < position("tagged"), "{}", output);
+    assert!(position("tagged") < position("important"), "{}", output);
+    assert!(position("important") < position("someday"), "{}", output);
+    assert!(output.contains("16.0"), "{}", output);
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{TestEnv, stderr, stdout};
+use serde_json::Value;
+use std::fs;
+
+fn run(env: &TestEnv, args: &[&str]) -> String {
+    let output = env.utodo(args);
+    assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+    stdout(&output)
+}
+
+fn config(env: &TestEnv) -> Value {
+    serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap()
+}
+
+#[test]
+fn saved_views_combine_filter_sort_columns_and_grouping() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    run(&env, &["add", "--title", "write report", "--priority", "low", "--tag", "work"]);
+    run(&env, &["add", "--title", "fix outage", "--priority", "high", "--tag", "work"]);
+    run(&env, &["add", "--title", "buy milk", "--priority", "high"]);
+    run(&env, &["add", "--title", "old work", "--tag", "work"]);
+    run(&env, &["complete", "3"]);
+
+    let saved = run(
+        &env,
+        &["view", "save", "mine", "--where", "tag:work and open", "--sort", "priority", "--columns", "id,title,tags"],
+    );
+    assert!(saved.contains("View 'mine' saved"), "{}", saved);
+    let view = &config(&env)["views"]["mine"];
+    assert_eq!(view["filter"], "tag:work and open");
+    assert_eq!(view["sort"][0], "Priority");
+    assert_eq!(view["columns"][2], "Tags");
+
+    let shown = run(&env, &["view", "mine"]);
+    assert!(shown.contains("mine"), "{}", shown);
+    assert!(shown.contains("tags") && !shown.contains("created_at"), "{}", shown);
+    assert!(!shown.contains("buy milk") && !shown.contains("old work"), "{}", shown);
+    let outage = shown.find("fix outage").expect("high priority todo shown");
+    let report = shown.find("write report").expect("low priority todo shown");
+    assert!(outage < report, "{}", shown);
+
+    run(&env, &["view", "save", "mine", "--where", "open", "--group-by", "priority"]);
+    let grouped = run(&env, &["view", "mine"]);
+    let high = grouped.find("High").unwrap();
+    let low = grouped.find("Low").unwrap();
+    assert!(high < grouped.find("buy milk").unwrap(), "{}", grouped);
+    assert!(low < grouped.find("write report").unwrap(), "{}", grouped);
+    assert!(!grouped.contains("Medium"), "{}", grouped);
+
+    let listed = run(&env, &["view", "list"]);
+    assert!(listed.contains("mine: where open, grouped by priority"), "{}", listed);
+    assert!(listed.contains("next (built-in)"), "{}", listed);
+
+    assert!(run(&env, &["view", "delete", "mine"]).contains("View 'mine' deleted"));
+    assert!(config(&env).get("views").is_none());
+    assert_eq!(env.utodo(&["view", "mine"]).status.code(), Some(3));
+}
+
+#[test]
+fn built_in_views_work_without_configuration() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let yesterday = (Utc::now() - Duration::days(1)).format("%d.%m.%Y").to_string();
+    run(&env, &["add", "--title", "late task", "--due-date", &yesterday]);
+    run(&env, &["add", "--title", "open task"]);
+    run(&env, &["add", "--title", "closed task", "--estimate", "30m"]);
+    run(&env, &["complete", "2"]);
+
+    let next = run(&env, &["view", "next"]);
+    assert!(next.contains("late task") && next.contains("open task"), "{}", next);
+    assert!(!next.contains("closed task") && next.contains("urgency"), "{}", next);
+
+    let overdue = run(&env, &["view", "overdue"]);
+    assert!(overdue.contains("late task") && !overdue.contains("open task"), "{}", overdue);
+
+    let done = run(&env, &["view", "done-this-week"]);
+    assert!(done.contains("closed task") && done.contains("30m"), "{}", done);
+    assert!(!done.contains("open task"), "{}", done);
+
+    // Saved views take precedence over built-in ones of the same name
+    run(&env, &["view", "save", "next", "--where", "finished"]);
+    let next = run(&env, &["view", "next"]);
+    assert!(next.contains("closed task") && !next.contains("open task"), "{}", next);
+    run(&env, &["view", "delete", "next"]);
+    let output = env.utodo(&["view", "delete", "next"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("built-in"), "{}", stderr(&output));
+}
+
+#[test]
+fn invalid_views_are_rejected() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert_eq!(env.utodo(&["view", "save", "bad", "--where", "color:red"]).status.code(), Some(2));
+    assert_eq!(env.utodo(&["view", "save", "bad", "--columns", "owner"]).status.code(), Some(2));
+    let output = env.utodo(&["view", "save", "list"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("subcommand"), "{}", stderr(&output));
+    assert!(config(&env).get("views").is_none());
+}
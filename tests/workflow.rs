@@ -0,0 +1,84 @@
+mod common;
+
+use common::{TestEnv, stderr, stdout};
+use std::fs;
+
+#[test]
+fn legacy_finished_flag_maps_to_workflow_states() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    fs::create_dir_all(env.data_dir()).unwrap();
+    fs::write(
+        env.data_dir().join("todos.json"),
+        concat!(
+            r#"{"id":0,"title":"old done","description":null,"finished":true,"priority":"Low","created_at":"2025-01-01T10:00:00Z","due_date":null}"#,
+            "\n",
+            r#"{"id":1,"title":"old open","description":null,"finished":false,"priority":"Low","created_at":"2025-01-01T10:00:00Z","due_date":null}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    let stats = stdout(&env.utodo(&["stats", "--json"]));
+    assert!(stats.contains("\"finished\": 1"));
+
+    let board = stdout(&env.utodo(&["board"]));
+    assert!(board.contains("todo (1)") && board.contains("done (1)"));
+}
+
+#[test]
+fn todos_move_through_custom_workflow() {
+    let env = TestEnv::new();
+    env.write_config(false);
+
+    let output = env.utodo(&["workflow", "todo", "doing", "review", "done"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(env.utodo(&["add", "--title", "task"]).status.success());
+
+    assert!(env.utodo(&["move", "0", "review"]).status.success());
+    let board = stdout(&env.utodo(&["board"]));
+    assert!(board.contains("review (1)"));
+    assert!(stdout(&env.utodo(&["stats", "--json"])).contains("\"open\": 1"));
+
+    assert!(env.utodo(&["move", "0", "done"]).status.success());
+    assert!(stdout(&env.utodo(&["stats", "--json"])).contains("\"finished\": 1"));
+
+    let output = env.utodo(&["move", "0", "blocked"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Unknown state"));
+}
+
+#[test]
+fn finished_todos_stay_finished_when_the_workflow_changes() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    assert!(env.utodo(&["add", "--title", "shipped"]).status.success());
+    assert!(env.utodo(&["add", "--title", "pending"]).status.success());
+    assert!(env.utodo(&["complete", "0"]).status.success());
+
+    let output = env.utodo(&["workflow", "open", "closed"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stats = stdout(&env.utodo(&["stats", "--json"]));
+    assert!(stats.contains("\"finished\": 1") && stats.contains("\"open\": 1"), "{}", stats);
+    let board = stdout(&env.utodo(&["board"]));
+    assert!(board.contains("open (1)") && board.contains("closed (1)"), "{}", board);
+
+    // Reopening moves the todo into the new workflow
+    assert!(env.utodo(&["update", "--id", "0", "--finished", "false"]).status.success());
+    let stats = stdout(&env.utodo(&["stats", "--json"]));
+    assert!(stats.contains("\"finished\": 0"), "{}", stats);
+}
+
+#[test]
+fn invalid_workflow_in_config_is_reported() {
+    let env = TestEnv::new();
+    env.write_config(false);
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(env.config_file()).unwrap()).unwrap();
+    config["workflow"] = serde_json::json!(["only"]);
+    fs::write(env.config_file(), config.to_string()).unwrap();
+
+    let output = env.utodo(&["show"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(stderr(&output).contains("Invalid workflow"), "{}", stderr(&output));
+}